@@ -1,29 +1,32 @@
-use crate::task::Question;
+use crate::{serialization::StoredQuestion, task::Question};
 use anyhow::{anyhow, Context, Ok, Result};
+use serde::{Deserialize, Serialize};
 
 // first draft implementation
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct Missing {
     pub items: Vec<SeqItem>,
     pub options: Vec<SeqItem>,
     pub solution: SeqItem,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct SeqItem {
     pub form: Form,
     pub size: Size,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Form {
     Star,
     Ampersand,
     Cover,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Size {
     Small,
     Medium,
@@ -105,6 +108,32 @@ impl Question for Missing {
             .ok_or(anyhow!(format!("Input is not in ranage {}", answer)))?;
         return Ok(answer == &self.solution);
     }
+
+    fn question_type(&self) -> &'static str {
+        "missing"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Missing(self.clone())
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let form = match self.solution.form {
+            Form::Star => "star",
+            Form::Ampersand => "ampersand",
+            Form::Cover => "cover",
+        };
+        let size = match self.solution.size {
+            Size::Small => "small",
+            Size::Medium => "medium",
+            Size::Big => "big",
+        };
+        vec![
+            String::from(self.question_type()),
+            format!("missing:{}", form),
+            format!("missing:{}", size),
+        ]
+    }
 }
 
 fn figure(item: &SeqItem) -> String {
@@ -0,0 +1,249 @@
+use crate::deck::{AnswerKind, Card, Deck};
+use anyhow::{anyhow, Context, Ok, Result};
+use std::{fs, path::Path};
+
+// Anki "Notes in Plain Text" exports: one note per line, fields separated by
+// tabs (or the `#separator` header), optional `#tags column:N` and friends.
+
+// allowed error for cards tagged as estimations, relative to the answer
+const ESTIMATION_TOLERANCE: f64 = 0.1;
+
+struct Header {
+    delimiter: u8,
+    html: bool,
+    tags_column: Option<usize>,
+    // notetype, deck and guid columns hold no card fields
+    skipped_columns: Vec<usize>,
+}
+
+/// Converts an Anki export into a deck and writes it to `target` as TOML,
+/// an existing `target` is replaced only with `overwrite`.
+pub fn import(source: &str, target: &str, overwrite: bool) -> Result<Deck> {
+    if !overwrite && Path::new(target).exists() {
+        return Err(anyhow!(format!(
+            "Deck `{}` already exists, use `--force` to overwrite it",
+            target
+        )));
+    }
+    let content = fs::read_to_string(source)
+        .with_context(|| format!("Can not read Anki export `{}`", source))?;
+    let is_csv = Path::new(source)
+        .extension()
+        .map(|extension| extension == "csv")
+        .unwrap_or(false);
+    let mut deck = parse(&content, if is_csv { b',' } else { b'\t' })?;
+    deck.name = Path::new(target)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    deck.validate()?;
+    fs::write(target, deck.to_toml()?)
+        .with_context(|| format!("Can not write deck `{}`", target))?;
+    Ok(deck)
+}
+
+pub fn parse(content: &str, default_delimiter: u8) -> Result<Deck> {
+    let header = parse_header(content, default_delimiter)?;
+    let body: String = content
+        .lines()
+        .skip_while(|line| line.starts_with('#'))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(header.delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(body.as_bytes());
+    let mut cards: Vec<Card> = vec![];
+    for record in reader.records() {
+        let record = record.context("Anki export is not valid")?;
+        let mut fields: Vec<String> = vec![];
+        let mut tags: Vec<String> = vec![];
+        for (index, value) in record.iter().enumerate() {
+            if header.tags_column == Some(index) {
+                tags = value.split_whitespace().map(String::from).collect();
+            } else if !header.skipped_columns.contains(&index) {
+                let value = if header.html {
+                    strip_html(value)
+                } else {
+                    String::from(value)
+                };
+                fields.push(String::from(value.trim()));
+            }
+        }
+        // without a header the third column of a basic note is tags
+        if header.tags_column.is_none() && fields.len() > 2 {
+            tags = fields[2].split_whitespace().map(String::from).collect();
+        }
+        if fields.len() < 2 || fields[0].is_empty() {
+            continue;
+        }
+        cards.push(card(fields[0].clone(), fields[1].clone(), tags));
+    }
+    Ok(Deck {
+        name: String::new(),
+        cards,
+    })
+}
+
+/// Answer kind chosen by tags, e.g. `integer`, `arithmetic` or `estimation`;
+/// falls back to text when the back of the card does not fit the kind.
+fn card(prompt: String, answer: String, tags: Vec<String>) -> Card {
+    let mut kind = AnswerKind::Text;
+    let mut tolerance: Option<f64> = None;
+    for tag in &tags {
+        match tag.to_lowercase().as_str() {
+            "integer" | "arithmetic" | "math" | "number" => kind = AnswerKind::Integer,
+            "real" => kind = AnswerKind::Real,
+            "estimation" | "estimate" => {
+                kind = AnswerKind::Real;
+                tolerance = answer
+                    .parse::<f64>()
+                    .ok()
+                    .map(|value| (value * ESTIMATION_TOLERANCE).abs());
+            }
+            _ => {}
+        }
+    }
+    let fits = match kind {
+        AnswerKind::Integer => answer.parse::<i64>().is_ok(),
+        AnswerKind::Real => answer.parse::<f64>().is_ok(),
+        _ => true,
+    };
+    if !fits {
+        kind = AnswerKind::Text;
+        tolerance = None;
+    }
+    Card {
+        prompt,
+        answer,
+        kind,
+        tolerance,
+        options: vec![],
+        tags,
+    }
+}
+
+fn parse_header(content: &str, default_delimiter: u8) -> Result<Header> {
+    let mut header = Header {
+        delimiter: default_delimiter,
+        html: false,
+        tags_column: None,
+        skipped_columns: vec![],
+    };
+    for line in content.lines().take_while(|line| line.starts_with('#')) {
+        let (key, value) = match line[1..].split_once(':') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let value = value.trim();
+        match key.trim() {
+            "separator" => {
+                header.delimiter = match value.to_lowercase().as_str() {
+                    "tab" => b'\t',
+                    "comma" => b',',
+                    "semicolon" => b';',
+                    "pipe" => b'|',
+                    "space" => b' ',
+                    "colon" => b':',
+                    _ if value.len() == 1 => value.as_bytes()[0],
+                    _ => return Err(anyhow!(format!("unknown separator `{}`", value))),
+                }
+            }
+            "html" => header.html = value == "true",
+            "tags column" => header.tags_column = Some(column_index(value)?),
+            "notetype column" | "deck column" | "guid column" => {
+                header.skipped_columns.push(column_index(value)?)
+            }
+            _ => {}
+        }
+    }
+    Ok(header)
+}
+
+// Anki numbers columns from 1
+fn column_index(value: &str) -> Result<usize> {
+    let column: usize = value
+        .parse()
+        .with_context(|| format!("Column is not an integer `{}`", value))?;
+    column
+        .checked_sub(1)
+        .ok_or(anyhow!(format!("Column is out of range `{}`", value)))
+}
+
+fn strip_html(value: &str) -> String {
+    let mut result = String::new();
+    let mut in_tag = false;
+    for symbol in value.chars() {
+        match symbol {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => result.push(symbol),
+            _ => {}
+        }
+    }
+    result
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+#[test]
+fn anki_export_with_header() -> Result<()> {
+    let content = "#separator:tab
+#html:true
+#deck column:1
+#tags column:4
+Peak::Numbers\t2^10\t<b>1024</b>\tarithmetic powers
+Peak::Numbers\t1000 * 1.1^5\t1610\testimation
+Peak::Numbers\tCapital of France\tParis&nbsp;\tgeo
+";
+    let deck = parse(content, b'\t')?;
+    assert_eq!(deck.cards.len(), 3);
+    assert_eq!(deck.cards[0].prompt, "2^10");
+    assert_eq!(deck.cards[0].answer, "1024");
+    assert_eq!(deck.cards[0].kind, AnswerKind::Integer);
+    assert_eq!(deck.cards[0].tags, vec!["arithmetic", "powers"]);
+    assert_eq!(deck.cards[1].kind, AnswerKind::Real);
+    assert_eq!(deck.cards[1].tolerance, Some(161.0));
+    assert_eq!(deck.cards[2].answer, "Paris");
+    assert_eq!(deck.cards[2].kind, AnswerKind::Text);
+    Ok(())
+}
+
+#[test]
+fn anki_csv_without_header() -> Result<()> {
+    let content = "\"7 * 8\",56,math
+\"sqrt, rounded\",seven,math
+";
+    let deck = parse(content, b',')?;
+    assert_eq!(deck.cards.len(), 2);
+    assert_eq!(deck.cards[0].kind, AnswerKind::Integer);
+    assert_eq!(deck.cards[1].prompt, "sqrt, rounded");
+    assert_eq!(deck.cards[1].kind, AnswerKind::Text);
+    let deck = Deck::from_toml(&deck.to_toml()?)?;
+    assert_eq!(deck.cards[0].tags, vec!["math"]);
+    Ok(())
+}
+
+#[test]
+fn anki_card_field_starting_with_hash() -> Result<()> {
+    let content = "#separator:tab
+#html:false
+2^10\t1024
+#hex of 255\tff
+";
+    let deck = parse(content, b'\t')?;
+    assert_eq!(deck.cards.len(), 2);
+    assert_eq!(deck.cards[1].prompt, "#hex of 255");
+    assert_eq!(deck.cards[1].answer, "ff");
+    Ok(())
+}
+
+#[test]
+fn anki_import_keeps_existing_deck() {
+    let error = import("missing.txt", "Cargo.toml", false).unwrap_err();
+    assert!(error.to_string().contains("already exists"));
+}
@@ -1,96 +1,519 @@
-use crate::task::Question;
-use anyhow::{Context, Ok, Result};
+use crate::{serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct Sum {
-    pub a: i32,
-    pub b: i32,
+    pub a: i64,
+    pub b: i64,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct Sub {
-    pub a: i32,
-    pub b: i32,
+    pub a: i64,
+    pub b: i64,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct Mul {
-    pub a: i32,
-    pub b: i32,
+    pub a: i64,
+    pub b: i64,
 }
 
+/// Euclidean division, `-7 div 2 = -4` so that the remainder is never negative.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct Div {
-    pub a: i32,
-    pub b: i32,
+    pub a: i64,
+    pub b: i64,
 }
 
+/// Euclidean remainder, `-7 mod 3 = 2`, always in `0..|b|`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct Mod {
-    pub a: i32,
-    pub b: i32,
+    pub a: i64,
+    pub b: i64,
+}
+
+/// Division answered with quotient and remainder, e.g. `17 div 5 = 3 r 2`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct DivRem {
+    pub a: i64,
+    pub b: i64,
+}
+
+/// Operator of exercises over non-integer numbers, e.g. fractions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    pub const ALL: [Operator; 4] = [Operator::Add, Operator::Sub, Operator::Mul, Operator::Div];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Operator::Add => "+",
+            Operator::Sub => "-",
+            Operator::Mul => "*",
+            Operator::Div => "div",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operator::Add => "add",
+            Operator::Sub => "sub",
+            Operator::Mul => "mul",
+            Operator::Div => "div",
+        }
+    }
+}
+
+/// A single arithmetic fact like `7 * 8`, tracked separately for spaced repetition.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Fact {
+    Sum(Sum),
+    Sub(Sub),
+    Mul(Mul),
+    Div(Div),
+    Mod(Mod),
+}
+
+impl Fact {
+    pub fn from_parts(question_type: &str, a: i64, b: i64) -> Result<Fact> {
+        match question_type {
+            "sum" => Ok(Fact::Sum(Sum { a, b })),
+            "sub" => Ok(Fact::Sub(Sub { a, b })),
+            "mul" => Ok(Fact::Mul(Mul { a, b })),
+            "div" => Ok(Fact::Div(Div { a, b })),
+            "mod" => Ok(Fact::Mod(Mod { a, b })),
+            _ => Err(anyhow!(format!("unknown fact type `{}`", question_type))),
+        }
+    }
+
+    pub fn question_type(&self) -> &'static str {
+        match self {
+            Fact::Sum(_) => "sum",
+            Fact::Sub(_) => "sub",
+            Fact::Mul(_) => "mul",
+            Fact::Div(_) => "div",
+            Fact::Mod(_) => "mod",
+        }
+    }
+
+    pub fn operands(&self) -> (i64, i64) {
+        match self {
+            Fact::Sum(value) => (value.a, value.b),
+            Fact::Sub(value) => (value.a, value.b),
+            Fact::Mul(value) => (value.a, value.b),
+            Fact::Div(value) => (value.a, value.b),
+            Fact::Mod(value) => (value.a, value.b),
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Fact::Sum(_) => "+",
+            Fact::Sub(_) => "-",
+            Fact::Mul(_) => "*",
+            Fact::Div(_) => "div",
+            Fact::Mod(_) => "mod",
+        }
+    }
+
+    /// `None` on division by zero or overflow.
+    pub fn result(&self) -> Option<i64> {
+        let (a, b) = self.operands();
+        match self {
+            Fact::Sum(_) => a.checked_add(b),
+            Fact::Sub(_) => a.checked_sub(b),
+            Fact::Mul(_) => a.checked_mul(b),
+            Fact::Div(_) => a.checked_div_euclid(b),
+            Fact::Mod(_) => a.checked_rem_euclid(b),
+        }
+    }
+
+    pub fn key(&self) -> String {
+        let (a, b) = self.operands();
+        format!("{} {} {}", self.question_type(), a, b)
+    }
+
+    pub fn into_question(self) -> Box<dyn Question> {
+        match self {
+            Fact::Sum(value) => Box::new(value),
+            Fact::Sub(value) => Box::new(value),
+            Fact::Mul(value) => Box::new(value),
+            Fact::Div(value) => Box::new(value),
+            Fact::Mod(value) => Box::new(value),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operand {
+    Left,
+    Right,
+}
+
+/// Inverse form of a fact with one operand hidden, e.g. `? * 6 = 42`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct MissingOperand {
+    pub fact: Fact,
+    pub hidden: Operand,
+}
+
+impl Question for MissingOperand {
+    fn body(&self) -> String {
+        let (a, b) = self.fact.operands();
+        let result = self.fact.result().unwrap_or_default();
+        match self.hidden {
+            Operand::Left => format!(
+                "? {} {} = {}",
+                self.fact.symbol(),
+                right_operand(b),
+                grouped(result)
+            ),
+            Operand::Right => format!(
+                "{} {} ? = {}",
+                grouped(a),
+                self.fact.symbol(),
+                grouped(result)
+            ),
+        }
+    }
+
+    // any operand giving the same result is right, e.g. `13` for `? div 4 = 3`
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = convert_to_i64(answer)?;
+        let (a, b) = self.fact.operands();
+        let (a, b) = match self.hidden {
+            Operand::Left => (answer, b),
+            Operand::Right => (a, answer),
+        };
+        let candidate = Fact::from_parts(self.fact.question_type(), a, b)?;
+        Ok(candidate.result().is_some() && candidate.result() == self.fact.result())
+    }
+
+    fn question_type(&self) -> &'static str {
+        self.fact.question_type()
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::MissingOperand(*self)
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("{}:inverse", self.question_type()),
+        ]
+    }
 }
 
 impl Question for Sum {
     fn body(&self) -> String {
-        return format!("{} + {} = ?", self.a, self.b);
+        return format!("{} + {} = ?", grouped(self.a), right_operand(self.b));
     }
 
     fn check(&self, answer: &String) -> Result<bool> {
-        let answer = convert_to_i32(answer)?;
-        let solution = self.a + self.b;
-        Ok(answer == solution)
+        check_fact(Fact::Sum(*self), answer)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "sum"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Sum(*self)
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![String::from(self.question_type())];
+        if self.a.abs() % 10 + self.b.abs() % 10 >= 10 {
+            patterns.push(String::from("sum:carry"));
+        }
+        patterns.extend(signed_pattern(self.question_type(), self.a, self.b));
+        patterns
+    }
+
+    fn fact(&self) -> Option<Fact> {
+        Some(Fact::Sum(*self))
     }
 }
 
 impl Question for Sub {
     fn body(&self) -> String {
-        return format!("{} - {} = ?", self.a, self.b);
+        return format!("{} - {} = ?", grouped(self.a), right_operand(self.b));
     }
 
     fn check(&self, answer: &String) -> Result<bool> {
-        let answer = convert_to_i32(answer)?;
-        let solution = self.a - self.b;
-        Ok(answer == solution)
+        check_fact(Fact::Sub(*self), answer)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "sub"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Sub(*self)
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![String::from(self.question_type())];
+        if self.a.abs() % 10 < self.b.abs() % 10 {
+            patterns.push(String::from("sub:borrow"));
+        }
+        if self.a < self.b {
+            patterns.push(String::from("sub:negative"));
+        }
+        patterns.extend(signed_pattern(self.question_type(), self.a, self.b));
+        patterns
+    }
+
+    fn fact(&self) -> Option<Fact> {
+        Some(Fact::Sub(*self))
     }
 }
 
 impl Question for Mul {
     fn body(&self) -> String {
-        return format!("{} * {} = ?", self.a, self.b);
+        return format!("{} * {} = ?", grouped(self.a), right_operand(self.b));
     }
 
     fn check(&self, answer: &String) -> Result<bool> {
-        let answer = convert_to_i32(answer)?;
-        let solution = self.a * self.b;
-        Ok(answer == solution)
+        check_fact(Fact::Mul(*self), answer)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "mul"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Mul(*self)
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![String::from(self.question_type())];
+        patterns.push(format!("mul:{}", self.a));
+        if self.a != self.b {
+            patterns.push(format!("mul:{}", self.b));
+        }
+        patterns.extend(signed_pattern(self.question_type(), self.a, self.b));
+        patterns
+    }
+
+    fn fact(&self) -> Option<Fact> {
+        Some(Fact::Mul(*self))
     }
 }
 
 impl Question for Div {
     fn body(&self) -> String {
-        return format!("{} div {} = ?", self.a, self.b);
+        return format!("{} div {} = ?", grouped(self.a), right_operand(self.b));
     }
 
     fn check(&self, answer: &String) -> Result<bool> {
-        let answer = convert_to_i32(answer)?;
-        let solution = self.a / self.b;
-        Ok(answer == solution)
+        check_fact(Fact::Div(*self), answer)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "div"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Div(*self)
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![
+            String::from(self.question_type()),
+            format!("div:{}", self.b),
+        ];
+        patterns.extend(signed_pattern(self.question_type(), self.a, self.b));
+        patterns
+    }
+
+    fn fact(&self) -> Option<Fact> {
+        Some(Fact::Div(*self))
     }
 }
 
 impl Question for Mod {
     fn body(&self) -> String {
-        return format!("{} mod {} = ?", self.a, self.b);
+        return format!("{} mod {} = ?", grouped(self.a), right_operand(self.b));
     }
 
     fn check(&self, answer: &String) -> Result<bool> {
-        let answer = convert_to_i32(answer)?;
-        let solution = self.a % self.b;
-        Ok(answer == solution)
+        check_fact(Fact::Mod(*self), answer)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "mod"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Mod(*self)
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![
+            String::from(self.question_type()),
+            format!("mod:{}", self.b),
+        ];
+        patterns.extend(signed_pattern(self.question_type(), self.a, self.b));
+        patterns
+    }
+
+    fn fact(&self) -> Option<Fact> {
+        Some(Fact::Mod(*self))
     }
 }
 
-fn convert_to_i32(value: &String) -> Result<i32> {
-    let value: i32 = value
-        .trim()
+impl Question for DivRem {
+    fn body(&self) -> String {
+        format!("{} div {} = ? r ?", grouped(self.a), right_operand(self.b))
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let (quotient, remainder) = parse_quotient_remainder(answer)?;
+        let solution = (
+            self.a.checked_div_euclid(self.b),
+            self.a.checked_rem_euclid(self.b),
+        );
+        Ok(solution == (Some(quotient), Some(remainder)))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "divrem"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::DivRem(*self)
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("divrem:{}", self.b),
+        ]
+    }
+}
+
+/// Parses `3 r 2`, `3r2`, `3 rem 2`, `3 remainder 2`, `3, 2` and `3 2`,
+/// a single number means no remainder.
+pub fn parse_quotient_remainder(value: &str) -> Result<(i64, i64)> {
+    let value = value.trim();
+    let error = || anyhow!(format!("Input is not a quotient and remainder `{}`", value));
+    let lowercase = value.to_lowercase();
+    let normalized = lowercase
+        .replace("remainder", " ")
+        .replace("rem", " ")
+        .replace(['r', ','], " ");
+    let parts: Vec<&str> = normalized.split_whitespace().collect();
+    let (quotient, remainder) = match parts.as_slice() {
+        [quotient] => (*quotient, "0"),
+        [quotient, remainder] => (*quotient, *remainder),
+        _ => return Err(error()),
+    };
+    let quotient = quotient.parse().map_err(|_| error())?;
+    let remainder = remainder.parse().map_err(|_| error())?;
+    Ok((quotient, remainder))
+}
+
+/// Negative right operands are wrapped in parentheses, `7 - (-3)`.
+fn right_operand(value: i64) -> String {
+    if value < 0 {
+        format!("({})", grouped(value))
+    } else {
+        grouped(value)
+    }
+}
+
+fn signed_pattern(question_type: &str, a: i64, b: i64) -> Option<String> {
+    if a < 0 || b < 0 {
+        Some(format!("{}:signed", question_type))
+    } else {
+        None
+    }
+}
+
+// the solution is computed with checked operations, so huge operands never panic
+fn check_fact(fact: Fact, answer: &String) -> Result<bool> {
+    let answer = convert_to_i64(answer)?;
+    let solution = fact
+        .result()
+        .ok_or(anyhow!(format!("No integer solution for `{}`", fact.key())))?;
+    Ok(answer == solution)
+}
+
+/// Digits grouped by three from the right for numbers from `10 000`, e.g. `12 345`.
+pub fn grouped(value: i64) -> String {
+    let digits = value.unsigned_abs().to_string();
+    if digits.len() < 5 {
+        return value.to_string();
+    }
+    let mut result = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            result.push(' ');
+        }
+        result.push(digit);
+    }
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{}{}", sign, result)
+}
+
+/// Parses an integer and accepts digit groups like `12 345` or `12_345`.
+pub fn convert_to_i64(value: &String) -> Result<i64> {
+    let value = value.trim();
+    let digits: String = value
+        .chars()
+        .filter(|symbol| !matches!(symbol, ' ' | '_' | '\u{a0}' | '\u{202f}'))
+        .collect();
+    digits
         .parse()
-        .with_context(|| format!("Input is not an integer `{}`", value.trim()))?;
-    return Ok(value);
+        .with_context(|| format!("Input is not an integer `{}`", value))
+}
+
+#[test]
+fn quotient_remainder_formats() -> Result<()> {
+    for value in [
+        "3 r 2",
+        "3r2",
+        "3 R 2",
+        "3 rem 2",
+        "3 remainder 2",
+        "3, 2",
+        "3 2",
+    ] {
+        assert_eq!(parse_quotient_remainder(value)?, (3, 2));
+    }
+    assert_eq!(parse_quotient_remainder("4")?, (4, 0));
+    assert!(parse_quotient_remainder("3 r 2 r 1").is_err());
+    assert!(parse_quotient_remainder("three").is_err());
+    Ok(())
+}
+
+#[test]
+fn grouped_digits() {
+    assert_eq!(grouped(1234), "1234");
+    assert_eq!(grouped(12345), "12 345");
+    assert_eq!(grouped(-1234567), "-1 234 567");
+    assert_eq!(grouped(i64::MIN), "-9 223 372 036 854 775 808");
+}
+
+#[test]
+fn check_does_not_overflow() -> Result<()> {
+    let question = Mul { a: i64::MAX, b: 2 };
+    assert!(question.check(&String::from("0")).is_err());
+    let question = Mul { a: 12345, b: 67 };
+    assert!(question.check(&String::from("827 115"))?);
+    assert!(question.check(&String::from("827_115"))?);
+    Ok(())
 }
@@ -0,0 +1,256 @@
+use crate::{serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Ok, Result};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Base {
+    Bin,
+    Oct,
+    Dec,
+    Hex,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BitOperator {
+    And,
+    Or,
+    Xor,
+    Shl,
+    Shr,
+}
+
+/// Writing `value` given in one base in another one, e.g. `0x2f -> bin = ?`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct BaseConversion {
+    pub value: u64,
+    pub from: Base,
+    pub to: Base,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Bitwise {
+    pub a: u64,
+    pub b: u64,
+    pub operator: BitOperator,
+}
+
+impl Base {
+    pub const ALL: [Base; 4] = [Base::Bin, Base::Oct, Base::Dec, Base::Hex];
+
+    pub fn radix(&self) -> u32 {
+        match self {
+            Base::Bin => 2,
+            Base::Oct => 8,
+            Base::Dec => 10,
+            Base::Hex => 16,
+        }
+    }
+
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Base::Bin => "0b",
+            Base::Oct => "0o",
+            Base::Dec => "",
+            Base::Hex => "0x",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Base::Bin => "bin",
+            Base::Oct => "oct",
+            Base::Dec => "dec",
+            Base::Hex => "hex",
+        }
+    }
+
+    pub fn format(&self, value: u64) -> String {
+        let digits = match self {
+            Base::Bin => format!("{:b}", value),
+            Base::Oct => format!("{:o}", value),
+            Base::Dec => format!("{}", value),
+            Base::Hex => format!("{:x}", value),
+        };
+        format!("{}{}", self.prefix(), digits)
+    }
+
+    /// Digits in this base, the base prefix is optional and `_` or spaces separate groups.
+    pub fn parse(&self, value: &str) -> Result<u64> {
+        let error = || anyhow!(format!("Input is not a {} number `{}`", self.name(), value));
+        let digits: String = value
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter(|symbol| !matches!(symbol, '_' | ' '))
+            .collect();
+        let digits = match self {
+            Base::Dec => digits.as_str(),
+            _ => digits.strip_prefix(self.prefix()).unwrap_or(&digits),
+        };
+        u64::from_str_radix(digits, self.radix()).map_err(|_| error())
+    }
+}
+
+/// Number in any base, chosen by its `0b`, `0o` or `0x` prefix, decimal without one.
+pub fn parse_prefixed(value: &str) -> Result<u64> {
+    let lowercase = value.trim().to_lowercase();
+    let base = Base::ALL
+        .into_iter()
+        .find(|base| *base != Base::Dec && lowercase.starts_with(base.prefix()))
+        .unwrap_or(Base::Dec);
+    base.parse(value)
+}
+
+impl BitOperator {
+    pub const ALL: [BitOperator; 5] = [
+        BitOperator::And,
+        BitOperator::Or,
+        BitOperator::Xor,
+        BitOperator::Shl,
+        BitOperator::Shr,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BitOperator::And => "&",
+            BitOperator::Or => "|",
+            BitOperator::Xor => "^",
+            BitOperator::Shl => "<<",
+            BitOperator::Shr => ">>",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BitOperator::And => "and",
+            BitOperator::Or => "or",
+            BitOperator::Xor => "xor",
+            BitOperator::Shl => "shl",
+            BitOperator::Shr => "shr",
+        }
+    }
+}
+
+impl Bitwise {
+    pub fn solution(&self) -> Option<u64> {
+        match self.operator {
+            BitOperator::And => Some(self.a & self.b),
+            BitOperator::Or => Some(self.a | self.b),
+            BitOperator::Xor => Some(self.a ^ self.b),
+            BitOperator::Shl => self.a.checked_shl(self.b as u32),
+            BitOperator::Shr => self.a.checked_shr(self.b as u32),
+        }
+    }
+}
+
+impl Question for BaseConversion {
+    fn body(&self) -> String {
+        format!("{} -> {} = ?", self.from.format(self.value), self.to.name())
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(self.to.parse(answer)? == self.value)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "base"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("base:{}", self.to.name()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Base(*self)
+    }
+}
+
+/// Operands of `&`, `|` and `^` are shown in binary, shifts in decimal.
+impl Question for Bitwise {
+    fn body(&self) -> String {
+        match self.operator {
+            BitOperator::Shl | BitOperator::Shr => {
+                format!("{} {} {} = ?", self.a, self.operator.symbol(), self.b)
+            }
+            _ => format!(
+                "{} {} {} = ?",
+                Base::Bin.format(self.a),
+                self.operator.symbol(),
+                Base::Bin.format(self.b)
+            ),
+        }
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = parse_prefixed(answer)?;
+        Ok(Some(answer) == self.solution())
+    }
+
+    fn question_type(&self) -> &'static str {
+        "bitwise"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("bitwise:{}", self.operator.name()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Bitwise(*self)
+    }
+}
+
+pub fn random_base_conversion() -> BaseConversion {
+    let mut rng = rand::thread_rng();
+    let from = *Base::ALL.choose(&mut rng).unwrap();
+    let to = loop {
+        let to = *Base::ALL.choose(&mut rng).unwrap();
+        if to != from {
+            break to;
+        }
+    };
+    BaseConversion {
+        value: rng.gen_range(0..256),
+        from,
+        to,
+    }
+}
+
+pub fn random_bitwise() -> Bitwise {
+    let mut rng = rand::thread_rng();
+    let operator = *BitOperator::ALL.choose(&mut rng).unwrap();
+    match operator {
+        BitOperator::Shl | BitOperator::Shr => Bitwise {
+            a: rng.gen_range(1..64),
+            b: rng.gen_range(1..5),
+            operator,
+        },
+        _ => Bitwise {
+            a: rng.gen_range(0..16),
+            b: rng.gen_range(0..16),
+            operator,
+        },
+    }
+}
+
+#[test]
+fn base_parse() -> Result<()> {
+    assert_eq!(Base::Hex.parse("0x2F")?, 47);
+    assert_eq!(Base::Hex.parse("2f")?, 47);
+    assert_eq!(Base::Bin.parse("0b1010_1010")?, 170);
+    assert_eq!(Base::Oct.parse("0o17")?, 15);
+    assert!(Base::Bin.parse("0x2f").is_err());
+    assert!(Base::Dec.parse("0b11").is_err());
+    assert_eq!(parse_prefixed("0b110")?, 6);
+    assert_eq!(parse_prefixed("0XFF")?, 255);
+    assert_eq!(parse_prefixed("12")?, 12);
+    Ok(())
+}
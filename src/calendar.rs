@@ -0,0 +1,211 @@
+use crate::{serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// English, German, French, Spanish and Russian names, Monday first
+const WEEKDAY_NAMES: [[&str; 7]; 6] = [
+    [
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "friday",
+        "saturday",
+        "sunday",
+    ],
+    [
+        "montag",
+        "dienstag",
+        "mittwoch",
+        "donnerstag",
+        "freitag",
+        "samstag",
+        "sonntag",
+    ],
+    [
+        "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+    ],
+    [
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+        "domingo",
+    ],
+    // Spanish names as typed without accents
+    [
+        "lunes",
+        "martes",
+        "miercoles",
+        "jueves",
+        "viernes",
+        "sabado",
+        "domingo",
+    ],
+    [
+        "понедельник",
+        "вторник",
+        "среда",
+        "четверг",
+        "пятница",
+        "суббота",
+        "воскресенье",
+    ],
+];
+// shorter prefixes like `m` or `s` are ambiguous in every language
+const MIN_PREFIX_LENGTH: usize = 2;
+// dates are shown with four digit years
+const MAX_YEAR: i32 = 9999;
+
+/// Weekday of a date of the Gregorian calendar.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Weekday {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// `1` for Monday up to `7` for Sunday as in ISO 8601.
+pub fn weekday_number(year: i32, month: u32, day: u32) -> u32 {
+    // Sakamoto's method, `0` is Sunday
+    const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 { year - 1 } else { year };
+    let weekday = (year + year.div_euclid(4) - year.div_euclid(100)
+        + year.div_euclid(400)
+        + OFFSETS[month as usize - 1]
+        + day as i32)
+        .rem_euclid(7);
+    if weekday == 0 {
+        7
+    } else {
+        weekday as u32
+    }
+}
+
+/// Accepts `1` to `7` from Monday, `0` for Sunday and weekday names or their prefixes
+/// in English, German, French, Spanish and Russian.
+pub fn parse_weekday(value: &str) -> Result<u32> {
+    let value = value.trim().to_lowercase();
+    let error = || format!("Input is not a weekday `{}`", value);
+    if value.chars().all(|symbol| symbol.is_ascii_digit()) {
+        let number: u32 = value.parse().with_context(error)?;
+        return match number {
+            0 => Ok(7),
+            1..=7 => Ok(number),
+            _ => Err(anyhow!(error())),
+        };
+    }
+    if value.chars().count() < MIN_PREFIX_LENGTH {
+        return Err(anyhow!(error()));
+    }
+    let mut matches: Vec<u32> = vec![];
+    for names in WEEKDAY_NAMES {
+        for (index, name) in names.iter().enumerate() {
+            let number = index as u32 + 1;
+            if name.starts_with(value.as_str()) && !matches.contains(&number) {
+                matches.push(number);
+            }
+        }
+    }
+    match matches.as_slice() {
+        [number] => Ok(*number),
+        [] => Err(anyhow!(error())),
+        _ => Err(anyhow!(format!("Weekday `{}` is ambiguous", value))),
+    }
+}
+
+impl Question for Weekday {
+    fn body(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02} = ? (weekday)",
+            self.year, self.month, self.day
+        )
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(parse_weekday(answer)? == weekday_number(self.year, self.month, self.day))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "weekday"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("weekday:{}", self.year.div_euclid(100)),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Weekday(*self)
+    }
+}
+
+/// Parses a year range like `1900-2099` within `1..=9999`, a single year is a range of one.
+pub fn parse_years(value: &str) -> Result<(i32, i32)> {
+    let error = || format!("Input is not a year range `{}`", value);
+    let (from, to) = value.split_once('-').unwrap_or((value, value));
+    let from: i32 = from.trim().parse().with_context(error)?;
+    let to: i32 = to.trim().parse().with_context(error)?;
+    if from > to || from < 1 || to > MAX_YEAR {
+        return Err(anyhow!(error()));
+    }
+    Ok((from, to))
+}
+
+pub fn random_weekday(years: (i32, i32)) -> Weekday {
+    let mut rng = rand::thread_rng();
+    let year = rng.gen_range(years.0..=years.1);
+    let month = rng.gen_range(1..=12);
+    Weekday {
+        year,
+        month,
+        day: rng.gen_range(1..=days_in_month(year, month)),
+    }
+}
+
+#[test]
+fn weekday_numbers() {
+    assert_eq!(weekday_number(2000, 1, 1), 6);
+    assert_eq!(weekday_number(1969, 7, 20), 7);
+    assert_eq!(weekday_number(2024, 2, 29), 4);
+    assert_eq!(weekday_number(1900, 3, 1), 4);
+}
+
+#[test]
+fn weekday_parse() -> Result<()> {
+    assert_eq!(parse_weekday("Friday")?, 5);
+    assert_eq!(parse_weekday("fr")?, 5);
+    assert_eq!(parse_weekday("Donnerstag")?, 4);
+    assert_eq!(parse_weekday("sábado")?, 6);
+    assert_eq!(parse_weekday("sabado")?, 6);
+    assert_eq!(parse_weekday("Среда")?, 3);
+    assert_eq!(parse_weekday("0")?, 7);
+    assert!(parse_weekday("8").is_err());
+    assert!(parse_weekday("m").is_err());
+    assert_eq!(parse_weekday("mo")?, 1);
+    assert!(parse_weekday("do").is_err());
+    assert!(parse_weekday("kek").is_err());
+    assert_eq!(parse_years("1900-2099")?, (1900, 2099));
+    assert!(parse_years("2099-1900").is_err());
+    assert!(parse_years("2000000000-2147483647").is_err());
+    assert_eq!(parse_years("9999")?, (9999, 9999));
+    Ok(())
+}
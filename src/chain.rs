@@ -0,0 +1,126 @@
+use crate::{arithmetic::Operator, serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+// keeps the running total small enough to hold in mind
+const MAX_TOTAL: i64 = 100;
+const MAX_MUL_TOTAL: i64 = 20;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Step {
+    pub operator: Operator,
+    pub operand: i64,
+}
+
+/// Running total shown one step at a time, only the final result is asked.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Chain {
+    pub start: i64,
+    pub steps: Vec<Step>,
+}
+
+impl Chain {
+    pub fn total(&self) -> Result<i64> {
+        let mut total = self.start;
+        for step in &self.steps {
+            total = match step.operator {
+                Operator::Add => total.checked_add(step.operand),
+                Operator::Sub => total.checked_sub(step.operand),
+                Operator::Mul => total.checked_mul(step.operand),
+                Operator::Div if step.operand != 0 && total % step.operand == 0 => {
+                    total.checked_div(step.operand)
+                }
+                Operator::Div => return Err(anyhow!("Division is not exact")),
+            }
+            .ok_or(anyhow!("Overflow"))?;
+        }
+        Ok(total)
+    }
+}
+
+impl Question for Chain {
+    fn body(&self) -> String {
+        String::from("= ?")
+    }
+
+    fn stages(&self) -> Vec<String> {
+        let mut stages = vec![format!("start at {}", self.start)];
+        for step in &self.steps {
+            stages.push(format!("{} {}", step.operator.symbol(), step.operand));
+        }
+        stages
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer: i64 = answer
+            .trim()
+            .parse()
+            .with_context(|| format!("Input is not an integer `{}`", answer.trim()))?;
+        Ok(answer == self.total()?)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "chain"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("chain:{}", self.steps.len()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Chain(self.clone())
+    }
+}
+
+/// Every intermediate total is a non-negative integer not above `MAX_TOTAL`.
+pub fn random_chain(steps: usize) -> Chain {
+    let mut rng = rand::thread_rng();
+    let start = rng.gen_range(1..21);
+    let mut total = start;
+    let mut chain = Chain {
+        start,
+        steps: vec![],
+    };
+    while chain.steps.len() < steps {
+        let operator = *Operator::ALL.choose(&mut rng).unwrap();
+        let operand = match operator {
+            Operator::Add if total < MAX_TOTAL => rng.gen_range(1..=(MAX_TOTAL - total).min(20)),
+            Operator::Sub if total > 0 => rng.gen_range(1..=total.min(20)),
+            Operator::Mul if total <= MAX_MUL_TOTAL => {
+                rng.gen_range(2..=(MAX_TOTAL / total.max(1)).clamp(2, 5))
+            }
+            Operator::Div => {
+                let divisors: Vec<i64> = (2..10).filter(|value| total % value == 0).collect();
+                match divisors.choose(&mut rng) {
+                    Some(value) if total > 0 => *value,
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        let step = Step { operator, operand };
+        chain.steps.push(step);
+        total = chain.total().unwrap_or(total);
+    }
+    chain
+}
+
+#[test]
+fn random_chain_is_small() {
+    for _ in 0..100 {
+        let chain = random_chain(5);
+        assert_eq!(chain.steps.len(), 5);
+        for length in 0..=chain.steps.len() {
+            let prefix = Chain {
+                start: chain.start,
+                steps: chain.steps[..length].to_vec(),
+            };
+            let total = prefix.total().unwrap();
+            assert!((0..=MAX_TOTAL).contains(&total));
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{percentage::parse_real, serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+const DAY_MINUTES: i64 = 24 * 60;
+
+/// Times of day are minutes since midnight.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "variant", rename_all = "lowercase")]
+pub enum ClockProblem {
+    /// `14:45 + 2h 35m = ?`, negative minutes go back, past midnight wraps around
+    Shift { time: i64, minutes: i64 },
+    /// duration from one time to a later one, over midnight if `to` is earlier
+    Difference { from: i64, to: i64 },
+    /// `155 min = ? (h:mm)`
+    Convert { minutes: i64 },
+    /// smaller angle between the hour and the minute hands in degrees
+    Angle { time: i64 },
+}
+
+pub fn format_time(minutes: i64) -> String {
+    let minutes = minutes.rem_euclid(DAY_MINUTES);
+    format!("{}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// `2h 35m`, `2h` or `35m`.
+pub fn format_duration(minutes: i64) -> String {
+    let (hours, minutes) = (minutes.abs() / 60, minutes.abs() % 60);
+    match (hours, minutes) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+/// Parses `17:20`, `5:20pm`, `5:20 am`, `5pm` and `17.20` into minutes since midnight.
+pub fn parse_time(value: &str) -> Result<i64> {
+    let value = value.trim().to_lowercase();
+    let error = || format!("Input is not a time `{}`", value);
+    let (clock, meridiem) = if let Some(rest) = value.strip_suffix("am") {
+        (rest.trim(), Some(0))
+    } else if let Some(rest) = value.strip_suffix("pm") {
+        (rest.trim(), Some(12))
+    } else {
+        (value.as_str(), None)
+    };
+    let (hours, minutes) = clock
+        .split_once([':', '.'])
+        .unwrap_or((clock, if meridiem.is_some() { "0" } else { "" }));
+    let hours: i64 = hours.trim().parse().with_context(error)?;
+    let minutes: i64 = minutes.trim().parse().with_context(error)?;
+    let hours = match meridiem {
+        Some(offset) if (1..=12).contains(&hours) => hours % 12 + offset,
+        Some(_) => return Err(anyhow!(error())),
+        None => hours,
+    };
+    if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+        return Err(anyhow!(error()));
+    }
+    Ok(hours * 60 + minutes)
+}
+
+/// Parses `2:35`, `2h 35m`, `2h35`, `2h`, `35m` and plain minutes like `155`.
+pub fn parse_duration(value: &str) -> Result<i64> {
+    let value = value.trim().to_lowercase().replace("min", "m");
+    let error = || format!("Input is not a duration `{}`", value);
+    if let Some((hours, minutes)) = value.split_once(':') {
+        let hours: i64 = hours.trim().parse().with_context(error)?;
+        let minutes: i64 = minutes.trim().parse().with_context(error)?;
+        if hours < 0 || !(0..60).contains(&minutes) {
+            return Err(anyhow!(error()));
+        }
+        return Ok(hours * 60 + minutes);
+    }
+    let (hours, minutes) = match value.split_once('h') {
+        Some((hours, minutes)) => (hours.trim(), minutes.trim()),
+        None => ("0", value.as_str()),
+    };
+    let minutes = minutes.strip_suffix('m').unwrap_or(minutes).trim();
+    let hours: i64 = hours.parse().with_context(error)?;
+    let minutes: i64 = if minutes.is_empty() {
+        0
+    } else {
+        minutes.parse().with_context(error)?
+    };
+    if hours < 0 || minutes < 0 {
+        return Err(anyhow!(error()));
+    }
+    Ok(hours * 60 + minutes)
+}
+
+/// Same as `parse_duration` without plain minutes, the answer must name the hours.
+pub fn parse_hours_minutes(value: &str) -> Result<i64> {
+    if !value.to_lowercase().contains([':', 'h']) {
+        return Err(anyhow!(format!(
+            "Input is not in hours and minutes `{}`",
+            value.trim()
+        )));
+    }
+    parse_duration(value)
+}
+
+impl ClockProblem {
+    fn name(&self) -> &'static str {
+        match self {
+            ClockProblem::Shift { .. } => "shift",
+            ClockProblem::Difference { .. } => "difference",
+            ClockProblem::Convert { .. } => "convert",
+            ClockProblem::Angle { .. } => "angle",
+        }
+    }
+
+    pub fn angle(time: i64) -> f64 {
+        let time = time.rem_euclid(DAY_MINUTES);
+        let hour_hand = (time % 720) as f64 * 0.5;
+        let minute_hand = (time % 60) as f64 * 6.0;
+        let angle = (hour_hand - minute_hand).abs();
+        angle.min(360.0 - angle)
+    }
+}
+
+impl Question for ClockProblem {
+    fn body(&self) -> String {
+        match *self {
+            ClockProblem::Shift { time, minutes } => {
+                let sign = if minutes < 0 { "-" } else { "+" };
+                format!(
+                    "{} {} {} = ?",
+                    format_time(time),
+                    sign,
+                    format_duration(minutes)
+                )
+            }
+            ClockProblem::Difference { from, to } => {
+                format!("{} -> {} = ? (h:mm)", format_time(from), format_time(to))
+            }
+            ClockProblem::Convert { minutes } => format!("{} min = ? (h:mm)", minutes),
+            ClockProblem::Angle { time } => format!("angle {} = ?", format_time(time)),
+        }
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let correct = match *self {
+            ClockProblem::Shift { time, minutes } => {
+                parse_time(answer)? == (time + minutes).rem_euclid(DAY_MINUTES)
+            }
+            ClockProblem::Difference { from, to } => {
+                parse_duration(answer)? == (to - from).rem_euclid(DAY_MINUTES)
+            }
+            ClockProblem::Convert { minutes } => parse_hours_minutes(answer)? == minutes,
+            ClockProblem::Angle { time } => {
+                parse_real(answer.trim().trim_end_matches('°'))? == ClockProblem::angle(time)
+            }
+        };
+        Ok(correct)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "clock"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("clock:{}", self.name()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Clock(*self)
+    }
+}
+
+// five minute steps as in real schedules
+pub fn random_clock_problem() -> ClockProblem {
+    let mut rng = rand::thread_rng();
+    let time = || rand::thread_rng().gen_range(0..DAY_MINUTES / 5) * 5;
+    match rng.gen_range(0..4) {
+        0 => ClockProblem::Shift {
+            time: time(),
+            minutes: rng.gen_range(-60..60) * 5,
+        },
+        1 => ClockProblem::Difference {
+            from: time(),
+            to: time(),
+        },
+        2 => ClockProblem::Convert {
+            minutes: rng.gen_range(61..600),
+        },
+        _ => ClockProblem::Angle {
+            time: rng.gen_range(0..144) * 5,
+        },
+    }
+}
+
+#[test]
+fn clock_parse() -> Result<()> {
+    assert_eq!(parse_time("17:20")?, 17 * 60 + 20);
+    assert_eq!(parse_time("5:20pm")?, 17 * 60 + 20);
+    assert_eq!(parse_time("5:20 AM")?, 5 * 60 + 20);
+    assert_eq!(parse_time("12am")?, 0);
+    assert_eq!(parse_time("12:30pm")?, 12 * 60 + 30);
+    assert_eq!(parse_time("17.20")?, 17 * 60 + 20);
+    assert!(parse_time("17").is_err());
+    assert!(parse_time("13pm").is_err());
+    assert!(parse_time("24:00").is_err());
+    assert_eq!(parse_duration("2:35")?, 155);
+    assert_eq!(parse_duration("2h 35m")?, 155);
+    assert_eq!(parse_duration("2h35")?, 155);
+    assert_eq!(parse_duration("2h")?, 120);
+    assert_eq!(parse_duration("35 min")?, 35);
+    assert_eq!(parse_duration("155")?, 155);
+    assert!(parse_duration("2:75").is_err());
+    assert_eq!(parse_hours_minutes("2H 35m")?, 155);
+    assert!(parse_hours_minutes("155").is_err());
+    assert!(parse_hours_minutes("155 min").is_err());
+    assert_eq!(ClockProblem::angle(3 * 60 + 30), 75.0);
+    assert_eq!(ClockProblem::angle(21 * 60), 90.0);
+    Ok(())
+}
@@ -0,0 +1,223 @@
+use crate::{arithmetic::Operator, serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Ok, Result};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, fmt};
+
+// enough for the operands of mental work and keeps `i64` far from overflow
+const MAX_DIGITS: usize = 15;
+// a quotient with more digits is not considered exact
+const MAX_QUOTIENT_SCALE: u32 = 10;
+/// Most decimal places of generated operands, products of two such operands
+/// still fit into `MAX_DIGITS`.
+pub const MAX_PLACES: u32 = 6;
+
+/// Exact decimal number `value / 10^scale`, e.g. `12.5` is `125` with scale `1`.
+#[derive(Debug, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Decimal {
+    pub value: i64,
+    pub scale: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct DecimalOp {
+    pub a: Decimal,
+    pub b: Decimal,
+    pub operator: Operator,
+}
+
+impl Decimal {
+    /// Parses `12.5`, `-0.05`, `.5` and accepts `,` as the decimal separator.
+    pub fn parse(value: &str) -> Result<Decimal> {
+        let value = value.trim();
+        let error = || anyhow!(format!("Input is not a decimal `{}`", value));
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let unsigned = unsigned.replace(',', ".");
+        let (whole, fractional) = unsigned.split_once('.').unwrap_or((&unsigned, ""));
+        let digits = format!("{}{}", whole, fractional);
+        if digits.is_empty()
+            || digits.len() > MAX_DIGITS
+            || !digits.chars().all(|symbol| symbol.is_ascii_digit())
+        {
+            return Err(error());
+        }
+        let value: i64 = digits.parse().map_err(|_| error())?;
+        Ok(Decimal {
+            value: if negative { -value } else { value },
+            scale: fractional.len() as u32,
+        })
+    }
+
+    /// Same number without trailing zeros, `1.50` becomes `1.5`.
+    pub fn normalized(&self) -> Decimal {
+        let mut result = *self;
+        while result.scale > 0 && result.value % 10 == 0 {
+            result.value /= 10;
+            result.scale -= 1;
+        }
+        result
+    }
+
+    pub fn apply(&self, operator: Operator, other: &Decimal) -> Result<Decimal> {
+        let overflow = || anyhow!("Overflow");
+        let result = match operator {
+            Operator::Add => {
+                let (a, b, scale) = aligned(self, other);
+                Decimal {
+                    value: i64::try_from(a + b).map_err(|_| overflow())?,
+                    scale,
+                }
+            }
+            Operator::Sub => {
+                let (a, b, scale) = aligned(self, other);
+                Decimal {
+                    value: i64::try_from(a - b).map_err(|_| overflow())?,
+                    scale,
+                }
+            }
+            Operator::Mul => Decimal {
+                value: self.value.checked_mul(other.value).ok_or_else(overflow)?,
+                scale: self.scale + other.scale,
+            },
+            Operator::Div => self.divide(other)?,
+        };
+        Ok(result.normalized())
+    }
+
+    fn divide(&self, other: &Decimal) -> Result<Decimal> {
+        if other.value == 0 {
+            return Err(anyhow!("Division by zero"));
+        }
+        let (a, b, _) = aligned(self, other);
+        for scale in 0..=MAX_QUOTIENT_SCALE {
+            let dividend = a * 10i128.pow(scale);
+            if dividend % b == 0 {
+                return Ok(Decimal {
+                    value: i64::try_from(dividend / b).map_err(|_| anyhow!("Overflow"))?,
+                    scale,
+                });
+            }
+        }
+        Err(anyhow!(format!(
+            "{} div {} is not a finite decimal",
+            self, other
+        )))
+    }
+}
+
+// `i128` as an answer may come with many more digits than the solution
+fn aligned(a: &Decimal, b: &Decimal) -> (i128, i128, u32) {
+    let scale = a.scale.max(b.scale);
+    (
+        a.value as i128 * 10i128.pow(scale - a.scale),
+        b.value as i128 * 10i128.pow(scale - b.scale),
+        scale,
+    )
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        let (a, b, _) = aligned(self, other);
+        a.cmp(&b)
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.value < 0 { "-" } else { "" };
+        let digits = format!(
+            "{:0width$}",
+            self.value.unsigned_abs(),
+            width = self.scale as usize + 1
+        );
+        let (whole, fractional) = digits.split_at(digits.len() - self.scale as usize);
+        if fractional.is_empty() {
+            write!(f, "{}{}", sign, whole)
+        } else {
+            write!(f, "{}{}.{}", sign, whole, fractional)
+        }
+    }
+}
+
+impl Question for DecimalOp {
+    fn body(&self) -> String {
+        format!("{} {} {} = ?", self.a, self.operator.symbol(), self.b)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = Decimal::parse(answer)?;
+        let solution = self.a.apply(self.operator, &self.b)?;
+        Ok(answer == solution)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "decimal"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("decimal:{}", self.operator.name()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Decimal(*self)
+    }
+}
+
+#[test]
+fn decimal_parse_and_display() -> Result<()> {
+    let parsed = Decimal::parse("12,50")?;
+    assert_eq!(
+        (parsed.value, parsed.scale, parsed.to_string()),
+        (1250, 2, String::from("12.50"))
+    );
+    assert_eq!(Decimal::parse("-0.05")?.to_string(), "-0.05");
+    assert_eq!(Decimal::parse(".5")?.to_string(), "0.5");
+    assert_eq!(Decimal::parse("7")?.to_string(), "7");
+    assert!(Decimal::parse("1.2.3").is_err());
+    assert!(Decimal::parse("1e3").is_err());
+    assert!(Decimal::parse("-").is_err());
+    Ok(())
+}
+
+#[test]
+fn decimal_apply() -> Result<()> {
+    let decimal = |value: &str| Decimal::parse(value).unwrap();
+    assert_eq!(
+        decimal("3.7").apply(Operator::Mul, &decimal("0.4"))?,
+        decimal("1.48")
+    );
+    assert_eq!(
+        decimal("12.5").apply(Operator::Sub, &decimal("7.86"))?,
+        decimal("4.64")
+    );
+    assert_eq!(
+        decimal("0.1").apply(Operator::Add, &decimal("0.2"))?,
+        decimal("0.3")
+    );
+    assert_eq!(
+        decimal("1.48").apply(Operator::Div, &decimal("0.4"))?,
+        decimal("3.7")
+    );
+    assert!(decimal("1").apply(Operator::Div, &decimal("3")).is_err());
+    assert!(decimal("999999999999999")
+        .apply(Operator::Mul, &decimal("999999999999999"))
+        .is_err());
+    Ok(())
+}
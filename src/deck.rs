@@ -0,0 +1,238 @@
+use crate::{serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, io::Read, path::Path};
+
+// user-written question files, e.g. in TOML:
+//
+// name = "ports"
+// [[cards]]
+// prompt = "HTTPS port"
+// answer = "443"
+// kind = "integer"
+//
+// or in CSV with a `prompt,answer,kind,tolerance,options,tags` header,
+// where choice options are separated by `|` and tags by spaces.
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AnswerKind {
+    Integer,
+    Real,
+    /// Trimmed answers must match exactly, case included.
+    #[default]
+    Text,
+    Choice,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Card {
+    pub prompt: String,
+    pub answer: String,
+    #[serde(default)]
+    pub kind: AnswerKind,
+    /// Allowed absolute difference for `real` answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<f64>,
+    /// Options for `choice` answers, `answer` is one of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Deck {
+    #[serde(default)]
+    pub name: String,
+    pub cards: Vec<Card>,
+}
+
+#[derive(Deserialize)]
+struct CsvCard {
+    prompt: String,
+    answer: String,
+    kind: Option<AnswerKind>,
+    tolerance: Option<f64>,
+    #[serde(default)]
+    options: String,
+    #[serde(default)]
+    tags: String,
+}
+
+impl Deck {
+    pub fn load(path: &str) -> Result<Deck> {
+        let file = Path::new(path);
+        let name = file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content =
+            fs::read_to_string(file).with_context(|| format!("Can not read deck `{}`", path))?;
+        let mut deck = match file.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Deck::from_toml(&content)?,
+            Some("csv") => Deck::from_csv(content.as_bytes())?,
+            _ => return Err(anyhow!(format!("unknown deck format `{}`", path))),
+        };
+        if deck.name.is_empty() {
+            deck.name = name;
+        }
+        deck.validate()?;
+        Ok(deck)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Deck> {
+        toml::from_str(content).context("Deck is not a valid TOML")
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).context("Deck can not be written as TOML")
+    }
+
+    pub fn from_csv(reader: impl Read) -> Result<Deck> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let mut cards: Vec<Card> = vec![];
+        for row in reader.deserialize() {
+            let row: CsvCard = row.context("Deck is not a valid CSV")?;
+            let options = row
+                .options
+                .split('|')
+                .map(|option| String::from(option.trim()))
+                .filter(|option| !option.is_empty())
+                .collect();
+            cards.push(Card {
+                prompt: row.prompt,
+                answer: row.answer,
+                kind: row.kind.unwrap_or_default(),
+                tolerance: row.tolerance,
+                options,
+                tags: row.tags.split_whitespace().map(String::from).collect(),
+            });
+        }
+        Ok(Deck {
+            name: String::new(),
+            cards,
+        })
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.cards.is_empty() {
+            return Err(anyhow!(format!("deck `{}` has no cards", self.name)));
+        }
+        for card in &self.cards {
+            let valid = match card.kind {
+                AnswerKind::Integer => card.answer.trim().parse::<i64>().is_ok(),
+                AnswerKind::Real => card.answer.trim().parse::<f64>().is_ok(),
+                AnswerKind::Text => true,
+                AnswerKind::Choice => card.options.contains(&card.answer),
+            };
+            if !valid {
+                return Err(anyhow!(format!(
+                    "card `{}` has invalid answer `{}`",
+                    card.prompt, card.answer
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Question for Card {
+    fn body(&self) -> String {
+        if self.kind != AnswerKind::Choice {
+            return self.prompt.clone();
+        }
+        let options: Vec<String> = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(pos, option)| format!("{}. {}", pos + 1, option))
+            .collect();
+        format!("{}\n{}", self.prompt, options.join(" "))
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = answer.trim();
+        match self.kind {
+            AnswerKind::Integer => {
+                let answer: i64 = answer
+                    .parse()
+                    .with_context(|| format!("Input is not an integer `{}`", answer))?;
+                Ok(Some(answer) == self.answer.trim().parse().ok())
+            }
+            AnswerKind::Real => {
+                let answer: f64 = answer
+                    .parse()
+                    .with_context(|| format!("Input is not a real number `{}`", answer))?;
+                let solution: f64 = self.answer.trim().parse()?;
+                Ok((answer - solution).abs() <= self.tolerance.unwrap_or(0.0))
+            }
+            AnswerKind::Text => Ok(answer == self.answer.trim()),
+            AnswerKind::Choice => {
+                let index: usize = answer
+                    .parse()
+                    .with_context(|| format!("Input is not an integer `{}`", answer))?;
+                let option = index
+                    .checked_sub(1)
+                    .and_then(|index| self.options.get(index))
+                    .ok_or(anyhow!(format!("Input is not in range {}", index)))?;
+                Ok(option == &self.answer)
+            }
+        }
+    }
+
+    fn question_type(&self) -> &'static str {
+        "deck"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![String::from(self.question_type())];
+        for tag in &self.tags {
+            patterns.push(format!("deck:{}", tag));
+        }
+        patterns
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Card(self.clone())
+    }
+}
+
+#[test]
+fn deck_from_toml() -> Result<()> {
+    let deck = Deck::from_toml(
+        r#"
+        name = "ports"
+        [[cards]]
+        prompt = "HTTPS port"
+        answer = "443"
+        kind = "integer"
+
+        [[cards]]
+        prompt = "DNS transport"
+        answer = "UDP"
+        kind = "choice"
+        options = ["TCP", "UDP"]
+        "#,
+    )?;
+    assert_eq!(deck.name, "ports");
+    assert_eq!(deck.cards.len(), 2);
+    assert_eq!(deck.cards[1].options, vec!["TCP", "UDP"]);
+    deck.validate()
+}
+
+#[test]
+fn deck_from_csv() -> Result<()> {
+    let content = "prompt,answer,kind,tolerance,options
+2^10,1024,integer,,
+L1 cache reference (ns),1,real,0.5,
+Capital of France,Paris,,,
+";
+    let deck = Deck::from_csv(content.as_bytes())?;
+    assert_eq!(deck.cards.len(), 3);
+    assert_eq!(deck.cards[1].tolerance, Some(0.5));
+    assert_eq!(deck.cards[2].kind, AnswerKind::Text);
+    deck.validate()
+}
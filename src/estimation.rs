@@ -0,0 +1,126 @@
+use crate::{
+    arithmetic::grouped,
+    percentage::parse_real,
+    serialization::StoredQuestion,
+    task::{Grade, Question},
+};
+use anyhow::{Ok, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum EstimateTarget {
+    Product { a: i64, b: i64 },
+    Quotient { a: i64, b: i64 },
+    Root { value: i64 },
+}
+
+/// Answered approximately, any answer within `max_error` percent of the solution is right,
+/// partial credit goes down to zero at twice the error.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Estimate {
+    pub target: EstimateTarget,
+    pub max_error: f64,
+}
+
+impl EstimateTarget {
+    pub fn solution(&self) -> f64 {
+        match self {
+            EstimateTarget::Product { a, b } => *a as f64 * *b as f64,
+            EstimateTarget::Quotient { a, b } => *a as f64 / *b as f64,
+            EstimateTarget::Root { value } => (*value as f64).sqrt(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            EstimateTarget::Product { .. } => "product",
+            EstimateTarget::Quotient { .. } => "quotient",
+            EstimateTarget::Root { .. } => "root",
+        }
+    }
+}
+
+impl Question for Estimate {
+    fn body(&self) -> String {
+        match self.target {
+            EstimateTarget::Product { a, b } => format!("{} * {} ~= ?", grouped(a), grouped(b)),
+            EstimateTarget::Quotient { a, b } => {
+                format!("{} div {} ~= ?", grouped(a), grouped(b))
+            }
+            EstimateTarget::Root { value } => format!("sqrt {} ~= ?", grouped(value)),
+        }
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(self.grade(answer)?.is_correct())
+    }
+
+    fn grade(&self, answer: &String) -> Result<Grade> {
+        let answer = parse_real(&answer.replace(' ', ""))?;
+        let error = relative_error(answer, self.target.solution());
+        let max_error = self.max_error / 100.0;
+        let score = (2.0 - error / max_error).clamp(0.0, 1.0);
+        Ok(Grade {
+            score,
+            error: Some(error),
+        })
+    }
+
+    fn question_type(&self) -> &'static str {
+        "estimate"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("estimate:{}", self.target.name()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Estimate(*self)
+    }
+}
+
+fn relative_error(answer: f64, solution: f64) -> f64 {
+    if solution == 0.0 {
+        return answer.abs();
+    }
+    ((answer - solution) / solution).abs()
+}
+
+pub fn random_estimate(max_error: f64) -> Estimate {
+    let mut rng = rand::thread_rng();
+    let target = match rng.gen_range(0..3) {
+        0 => EstimateTarget::Product {
+            a: rng.gen_range(100..1000),
+            b: rng.gen_range(11..100),
+        },
+        1 => EstimateTarget::Quotient {
+            a: rng.gen_range(1000..100000),
+            b: rng.gen_range(11..100),
+        },
+        _ => EstimateTarget::Root {
+            value: rng.gen_range(100..10000),
+        },
+    };
+    Estimate { target, max_error }
+}
+
+#[test]
+fn estimate_grade() -> Result<()> {
+    let question = Estimate {
+        target: EstimateTarget::Product { a: 487, b: 23 },
+        max_error: 10.0,
+    };
+    let grade = question.grade(&String::from("11201"))?;
+    assert_eq!((grade.score, grade.error), (1.0, Some(0.0)));
+    let grade = question.grade(&String::from("10 000"))?;
+    assert!((grade.error.unwrap() - 0.1072).abs() < 0.0001);
+    assert!((grade.score - 0.928).abs() < 0.001);
+    assert_eq!(question.grade(&String::from("20000"))?.score, 0.0);
+    assert!(question.grade(&String::from("kek")).is_err());
+    Ok(())
+}
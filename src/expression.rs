@@ -0,0 +1,403 @@
+use crate::{arithmetic::Operator, serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// long products may overflow
+const GENERATION_ATTEMPTS: u32 = 1000;
+
+/// Expression tree, every node evaluates to an integer.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "node", rename_all = "lowercase")]
+pub enum Expr {
+    Number {
+        value: i64,
+    },
+    Binary {
+        operator: Operator,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Expression {
+    pub root: Expr,
+}
+
+/// Shape of generated expressions.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExpressionConfig {
+    pub operators: Vec<Operator>,
+    pub operands: usize,
+    /// Allow trees which need parentheses, otherwise only the order of operations applies.
+    pub parentheses: bool,
+}
+
+impl Default for ExpressionConfig {
+    fn default() -> ExpressionConfig {
+        ExpressionConfig {
+            operators: Operator::ALL.to_vec(),
+            operands: 4,
+            parentheses: false,
+        }
+    }
+}
+
+/// Parses operator symbols like `+-*/`, separators are ignored.
+pub fn parse_operators(value: &str) -> Result<Vec<Operator>> {
+    let mut operators = vec![];
+    for symbol in value.chars().filter(|symbol| !matches!(symbol, ' ' | ',')) {
+        let operator = match symbol {
+            '+' => Operator::Add,
+            '-' => Operator::Sub,
+            '*' => Operator::Mul,
+            '/' => Operator::Div,
+            _ => return Err(anyhow!(format!("unknown operator `{}`", symbol))),
+        };
+        if !operators.contains(&operator) {
+            operators.push(operator);
+        }
+    }
+    if operators.is_empty() {
+        return Err(anyhow!("no operators"));
+    }
+    Ok(operators)
+}
+
+fn precedence(operator: Operator) -> u8 {
+    match operator {
+        Operator::Add | Operator::Sub => 1,
+        Operator::Mul | Operator::Div => 2,
+    }
+}
+
+impl Expr {
+    pub fn number(value: i64) -> Expr {
+        Expr::Number { value }
+    }
+
+    pub fn binary(operator: Operator, left: Expr, right: Expr) -> Expr {
+        Expr::Binary {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Fails on a division with a remainder, so every intermediate result is an integer.
+    pub fn evaluate(&self) -> Result<i64> {
+        let (operator, left, right) = match self {
+            Expr::Number { value } => return Ok(*value),
+            Expr::Binary {
+                operator,
+                left,
+                right,
+            } => (*operator, left.evaluate()?, right.evaluate()?),
+        };
+        let result = match operator {
+            Operator::Add => left.checked_add(right),
+            Operator::Sub => left.checked_sub(right),
+            Operator::Mul => left.checked_mul(right),
+            Operator::Div => {
+                if right == 0 || left % right != 0 {
+                    return Err(anyhow!(format!("{} div {} is not exact", left, right)));
+                }
+                left.checked_div(right)
+            }
+        };
+        result.ok_or(anyhow!("Overflow"))
+    }
+
+    pub fn operators(&self) -> Vec<Operator> {
+        match self {
+            Expr::Number { .. } => vec![],
+            Expr::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                let mut operators = left.operators();
+                operators.push(*operator);
+                operators.extend(right.operators());
+                operators
+            }
+        }
+    }
+
+    pub fn has_parentheses(&self) -> bool {
+        match self {
+            Expr::Number { .. } => false,
+            Expr::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                needs_parentheses(*operator, left, false)
+                    || needs_parentheses(*operator, right, true)
+                    || left.has_parentheses()
+                    || right.has_parentheses()
+            }
+        }
+    }
+}
+
+// the right operand of `-` and `div` binds to the left one, `8 - (2 + 1)`
+fn needs_parentheses(parent: Operator, child: &Expr, is_right: bool) -> bool {
+    match child {
+        Expr::Number { .. } => false,
+        Expr::Binary { operator, .. } => {
+            let (parent_precedence, child_precedence) = (precedence(parent), precedence(*operator));
+            child_precedence < parent_precedence
+                || (is_right
+                    && child_precedence == parent_precedence
+                    && !(parent == *operator && matches!(parent, Operator::Add | Operator::Mul)))
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Number { value } => write!(f, "{}", value),
+            Expr::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                let side = |child: &Expr, is_right: bool| {
+                    if needs_parentheses(*operator, child, is_right) {
+                        format!("({})", child)
+                    } else {
+                        child.to_string()
+                    }
+                };
+                write!(
+                    f,
+                    "{} {} {}",
+                    side(left, false),
+                    operator.symbol(),
+                    side(right, true)
+                )
+            }
+        }
+    }
+}
+
+impl Question for Expression {
+    fn body(&self) -> String {
+        format!("{} = ?", self.root)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer: i64 = answer
+            .trim()
+            .parse()
+            .with_context(|| format!("Input is not an integer `{}`", answer.trim()))?;
+        Ok(answer == self.root.evaluate()?)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "expression"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![String::from(self.question_type())];
+        let operators = self.root.operators();
+        for operator in Operator::ALL {
+            if operators.contains(&operator) {
+                patterns.push(format!("expression:{}", operator.name()));
+            }
+        }
+        if self.root.has_parentheses() {
+            patterns.push(String::from("expression:parentheses"));
+        }
+        patterns
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Expression(self.clone())
+    }
+}
+
+pub fn random_expression(config: &ExpressionConfig) -> Expression {
+    let generate = |operands: usize| {
+        if config.parentheses {
+            random_tree(operands, &config.operators)
+        } else {
+            random_flat(operands, &config.operators)
+        }
+    };
+    // divisions are always exact, only an overflow can fail and fewer operands avoid it
+    for operands in (2..=config.operands.max(2)).rev() {
+        for _ in 0..GENERATION_ATTEMPTS {
+            let root = generate(operands);
+            if root.evaluate().is_ok() {
+                return Expression { root };
+            }
+        }
+    }
+    // two operands up to 20 never overflow
+    Expression {
+        root: random_flat(2, &config.operators),
+    }
+}
+
+fn random_operand() -> Expr {
+    Expr::number(rand::thread_rng().gen_range(1..21))
+}
+
+// multiplies the first number of a `*` and `div` chain, which multiplies its value
+// and keeps every division in it exact, `None` on an overflow
+fn scale_first(chain: &Expr, factor: i64) -> Option<Expr> {
+    match chain {
+        Expr::Number { value } => Some(Expr::number(value.checked_mul(factor)?)),
+        Expr::Binary {
+            operator,
+            left,
+            right,
+        } => Some(Expr::binary(
+            *operator,
+            scale_first(left, factor)?,
+            (**right).clone(),
+        )),
+    }
+}
+
+// any tree shape, parentheses appear where the shape needs them,
+// the dividend of a division is a number: the quotient times the divisor
+fn random_tree(operands: usize, operators: &[Operator]) -> Expr {
+    if operands == 1 {
+        return random_operand();
+    }
+    let mut rng = rand::thread_rng();
+    let operator = *operators.choose(&mut rng).unwrap();
+    if operator == Operator::Div {
+        let quotient = rng.gen_range(1..21);
+        let divisor = random_tree(operands - 1, operators);
+        // a zero or failing divisor fails the whole tree, which is generated again
+        let dividend = divisor
+            .evaluate()
+            .ok()
+            .and_then(|value| value.checked_abs()?.checked_mul(quotient))
+            .unwrap_or(0);
+        return Expr::binary(operator, Expr::number(dividend), divisor);
+    }
+    let left = rng.gen_range(1..operands);
+    Expr::binary(
+        operator,
+        random_tree(left, operators),
+        random_tree(operands - left, operators),
+    )
+}
+
+// a sequence like `12 + 3 * 4 - 6 div 2` built by the order of operations,
+// before a division the chain so far is scaled by the divisor
+fn random_flat(operands: usize, operators: &[Operator]) -> Expr {
+    let mut rng = rand::thread_rng();
+    let mut terms = vec![random_operand()];
+    let mut additive: Vec<Operator> = vec![];
+    for _ in 1..operands {
+        let operator = *operators.choose(&mut rng).unwrap();
+        if precedence(operator) == 2 {
+            let mut left = terms.pop().unwrap();
+            let right = random_operand();
+            if let (Operator::Div, Expr::Number { value }) = (operator, &right) {
+                left = scale_first(&left, *value).unwrap_or(left);
+            }
+            terms.push(Expr::binary(operator, left, right));
+        } else {
+            additive.push(operator);
+            terms.push(random_operand());
+        }
+    }
+    let mut terms = terms.into_iter();
+    let first = terms.next().unwrap();
+    additive
+        .into_iter()
+        .zip(terms)
+        .fold(first, |left, (operator, right)| {
+            Expr::binary(operator, left, right)
+        })
+}
+
+#[test]
+fn expression_display_and_evaluate() -> Result<()> {
+    let number = Expr::number;
+    // 12 + 3 * 4 - 6 div 2
+    let flat = Expr::binary(
+        Operator::Sub,
+        Expr::binary(
+            Operator::Add,
+            number(12),
+            Expr::binary(Operator::Mul, number(3), number(4)),
+        ),
+        Expr::binary(Operator::Div, number(6), number(2)),
+    );
+    assert_eq!(flat.to_string(), "12 + 3 * 4 - 6 div 2");
+    assert_eq!(flat.evaluate()?, 21);
+    assert!(!flat.has_parentheses());
+    let nested = Expr::binary(
+        Operator::Mul,
+        Expr::binary(Operator::Add, number(2), number(3)),
+        Expr::binary(Operator::Sub, number(8), number(4)),
+    );
+    assert_eq!(nested.to_string(), "(2 + 3) * (8 - 4)");
+    assert_eq!(nested.evaluate()?, 20);
+    let right = Expr::binary(
+        Operator::Sub,
+        number(8),
+        Expr::binary(Operator::Sub, number(3), number(1)),
+    );
+    assert_eq!(right.to_string(), "8 - (3 - 1)");
+    assert!(Expr::binary(Operator::Div, number(7), number(2))
+        .evaluate()
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn random_expression_is_exact() {
+    for parentheses in [false, true] {
+        let config = ExpressionConfig {
+            operators: Operator::ALL.to_vec(),
+            operands: 5,
+            parentheses,
+        };
+        for _ in 0..100 {
+            let expression = random_expression(&config);
+            assert!(expression.root.evaluate().is_ok());
+            assert_eq!(expression.root.operators().len(), 4);
+            if !parentheses {
+                assert!(!expression.root.has_parentheses());
+            }
+        }
+    }
+}
+
+#[test]
+fn random_expression_never_overflows() {
+    let config = ExpressionConfig {
+        operators: vec![Operator::Mul],
+        operands: 30,
+        parentheses: false,
+    };
+    assert!(random_expression(&config).root.evaluate().is_ok());
+}
+
+#[test]
+fn random_expression_keeps_operators() {
+    for parentheses in [false, true] {
+        let config = ExpressionConfig {
+            operators: vec![Operator::Div],
+            operands: 6,
+            parentheses,
+        };
+        for _ in 0..100 {
+            let expression = random_expression(&config);
+            assert!(expression.root.evaluate().is_ok());
+            assert_eq!(expression.root.operators(), vec![Operator::Div; 5]);
+        }
+    }
+}
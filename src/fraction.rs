@@ -0,0 +1,249 @@
+use crate::{arithmetic::Operator, serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Fraction {
+    pub numerator: i64,
+    pub denominator: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct FractionOp {
+    pub a: Fraction,
+    pub b: Fraction,
+    pub operator: Operator,
+    /// Accept only answers in lowest terms, `2/4` is wrong for `1/2`.
+    pub simplified_only: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct FractionSimplify {
+    pub value: Fraction,
+}
+
+impl Fraction {
+    pub fn new(numerator: i64, denominator: i64) -> Result<Fraction> {
+        if denominator == 0 {
+            return Err(anyhow!("Denominator is zero"));
+        }
+        // the sign always lives in the numerator
+        let sign = denominator.signum();
+        match (numerator.checked_mul(sign), denominator.checked_mul(sign)) {
+            (Some(numerator), Some(denominator)) => Ok(Fraction {
+                numerator,
+                denominator,
+            }),
+            _ => Err(anyhow!("Overflow")),
+        }
+    }
+
+    /// Parses `3/4`, `-5/2`, `2` and mixed numbers like `1 1/2` or `-1 1/2`.
+    pub fn parse(value: &str) -> Result<Fraction> {
+        let value = value.trim();
+        let error = || format!("Input is not a fraction `{}`", value);
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, value),
+        };
+        let parts: Vec<&str> = unsigned.split_whitespace().collect();
+        let (whole, fraction) = match parts.as_slice() {
+            [fraction] if fraction.contains('/') => (0, *fraction),
+            [whole] => (whole.parse::<i64>().with_context(error)?, "0/1"),
+            [whole, fraction] if fraction.contains('/') => {
+                (whole.parse::<i64>().with_context(error)?, *fraction)
+            }
+            _ => return Err(anyhow!(error())),
+        };
+        let (numerator, denominator) = fraction.split_once('/').ok_or(anyhow!(error()))?;
+        let numerator: i64 = numerator.trim().parse().with_context(error)?;
+        let denominator: i64 = denominator.trim().parse().with_context(error)?;
+        if whole < 0 || numerator < 0 || denominator <= 0 {
+            return Err(anyhow!(error()));
+        }
+        let numerator = whole
+            .checked_mul(denominator)
+            .and_then(|whole| whole.checked_add(numerator))
+            .ok_or(anyhow!(error()))?;
+        Fraction::new(if negative { -numerator } else { numerator }, denominator)
+    }
+
+    pub fn simplified(&self) -> Fraction {
+        let divisor = gcd(self.numerator, self.denominator).max(1);
+        Fraction {
+            numerator: self.numerator / divisor,
+            denominator: self.denominator / divisor,
+        }
+    }
+
+    pub fn is_simplified(&self) -> bool {
+        gcd(self.numerator, self.denominator) == 1
+    }
+
+    pub fn is_proper(&self) -> bool {
+        self.numerator.abs() < self.denominator
+    }
+
+    pub fn apply(&self, operator: Operator, other: &Fraction) -> Result<Fraction> {
+        let (a, b, c, d) = (
+            self.numerator,
+            self.denominator,
+            other.numerator,
+            other.denominator,
+        );
+        let overflow = || anyhow!("Overflow");
+        let product = |x: i64, y: i64| x.checked_mul(y).ok_or_else(overflow);
+        let result = match operator {
+            Operator::Add => Fraction::new(
+                product(a, d)?
+                    .checked_add(product(c, b)?)
+                    .ok_or_else(overflow)?,
+                product(b, d)?,
+            )?,
+            Operator::Sub => Fraction::new(
+                product(a, d)?
+                    .checked_sub(product(c, b)?)
+                    .ok_or_else(overflow)?,
+                product(b, d)?,
+            )?,
+            Operator::Mul => Fraction::new(product(a, c)?, product(b, d)?)?,
+            Operator::Div => Fraction::new(product(a, d)?, product(b, c)?)?,
+        };
+        Ok(result.simplified())
+    }
+
+    pub fn equals(&self, other: &Fraction) -> Result<bool> {
+        let left = self.numerator.checked_mul(other.denominator);
+        let right = other.numerator.checked_mul(self.denominator);
+        match (left, right) {
+            (Some(left), Some(right)) => Ok(left == right),
+            _ => Err(anyhow!("Overflow")),
+        }
+    }
+}
+
+/// Improper fractions are shown as mixed numbers, e.g. `1 1/2`,
+/// unless the fractional part is zero.
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            return write!(f, "{}", self.numerator);
+        }
+        if self.is_proper() || self.numerator % self.denominator == 0 {
+            return write!(f, "{}/{}", self.numerator, self.denominator);
+        }
+        let sign = if self.numerator < 0 { "-" } else { "" };
+        let numerator = self.numerator.abs();
+        write!(
+            f,
+            "{}{} {}/{}",
+            sign,
+            numerator / self.denominator,
+            numerator % self.denominator,
+            self.denominator
+        )
+    }
+}
+
+impl Question for FractionOp {
+    fn body(&self) -> String {
+        format!("{} {} {} = ?", self.a, self.operator.symbol(), self.b)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = Fraction::parse(answer)?;
+        let solution = self.a.apply(self.operator, &self.b)?;
+        if self.simplified_only && !answer.is_simplified() {
+            return Ok(false);
+        }
+        answer.equals(&solution)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "fraction"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![
+            String::from(self.question_type()),
+            format!("fraction:{}", self.operator.name()),
+        ];
+        if self.a.denominator != self.b.denominator {
+            patterns.push(String::from("fraction:unlike"));
+        }
+        if !self.a.is_proper() || !self.b.is_proper() {
+            patterns.push(String::from("fraction:mixed"));
+        }
+        patterns
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Fraction(*self)
+    }
+}
+
+impl Question for FractionSimplify {
+    fn body(&self) -> String {
+        format!(
+            "simplify {}/{} = ?",
+            self.value.numerator, self.value.denominator
+        )
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = Fraction::parse(answer)?;
+        Ok(answer.is_simplified() && answer.equals(&self.value)?)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "simplify"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let mut patterns = vec![String::from(self.question_type())];
+        if !self.value.is_proper() {
+            patterns.push(String::from("simplify:mixed"));
+        }
+        patterns
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Simplify(*self)
+    }
+}
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[test]
+fn fraction_parse() -> Result<()> {
+    assert_eq!(Fraction::parse("3/4")?, Fraction::new(3, 4)?);
+    assert_eq!(Fraction::parse(" 1 1/2 ")?, Fraction::new(3, 2)?);
+    assert_eq!(Fraction::parse("-1 1/2")?, Fraction::new(-3, 2)?);
+    assert_eq!(Fraction::parse("-5/2")?, Fraction::new(-5, 2)?);
+    assert_eq!(Fraction::parse("2")?, Fraction::new(2, 1)?);
+    assert!(Fraction::parse("1/0").is_err());
+    assert!(Fraction::parse("1 -1/2").is_err());
+    assert!(Fraction::parse("kek").is_err());
+    assert!(Fraction::parse("99999999999 99999999999/99999999999").is_err());
+    let big = Fraction::new(i64::MAX, 1)?;
+    assert!(big.apply(Operator::Mul, &big).is_err());
+    assert!(big.equals(&Fraction::new(1, i64::MAX)?).is_err());
+    Ok(())
+}
+
+#[test]
+fn fraction_display() -> Result<()> {
+    assert_eq!(Fraction::new(3, 4)?.to_string(), "3/4");
+    assert_eq!(Fraction::new(7, 4)?.to_string(), "1 3/4");
+    assert_eq!(Fraction::new(-7, 4)?.to_string(), "-1 3/4");
+    assert_eq!(Fraction::new(4, -2)?.to_string(), "-4/2");
+    assert_eq!(Fraction::new(4, 2)?.simplified().to_string(), "2");
+    Ok(())
+}
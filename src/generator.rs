@@ -0,0 +1,330 @@
+use crate::{
+    abstract_sequence::{all_combinations, Missing, SeqItem},
+    arithmetic::{Div, DivRem, MissingOperand, Mod, Mul, Operand, Operator, Sub, Sum},
+    bits::{random_base_conversion, random_bitwise},
+    calendar::random_weekday,
+    chain::random_chain,
+    clock::random_clock_problem,
+    decimal::{Decimal, DecimalOp},
+    estimation::random_estimate,
+    expression::{random_expression, ExpressionConfig},
+    fraction::{Fraction, FractionOp, FractionSimplify},
+    money::{random_money_problem, Locale},
+    number_theory::{random_divisible, random_factorization, random_gcd, random_lcm, random_prime},
+    percentage::{PercentProblem, PercentVariant, Tolerance},
+    power::{random_power, random_root},
+    sequence::random_sequence,
+    task::Question,
+    units::{default_units, random_conversion, Unit},
+};
+use rand::{seq::SliceRandom, Rng};
+
+/// Most digits of big-number operands, 16 digits times 2 digits still fits `i64`.
+pub const MAX_DIGITS: u32 = 16;
+
+/// Question types which can be generated randomly.
+pub const GENERATED_TYPES: [&str; 28] = [
+    "sum",
+    "sub",
+    "mul",
+    "div",
+    "divrem",
+    "mod",
+    "percent",
+    "missing",
+    "fraction",
+    "simplify",
+    "decimal",
+    "expression",
+    "chain",
+    "power",
+    "root",
+    "estimate",
+    "base",
+    "bitwise",
+    "gcd",
+    "lcm",
+    "prime",
+    "divisible",
+    "factor",
+    "weekday",
+    "clock",
+    "unit",
+    "money",
+    "sequence",
+];
+
+/// Session options for generated questions.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GeneratorConfig {
+    /// Fraction answers must be in lowest terms.
+    pub simplified_fractions: bool,
+    /// Decimal places of decimal operands.
+    pub decimal_places: u32,
+    /// How close a percent answer must be to the solution.
+    pub percent_tolerance: Tolerance,
+    /// How close an approximate square root answer must be to the solution.
+    pub root_tolerance: Tolerance,
+    /// Arithmetic facts are asked with a hidden operand, e.g. `? + 7 = 15`.
+    pub missing_operands: bool,
+    pub expression: ExpressionConfig,
+    /// Steps of a running total after the start number.
+    pub chain_steps: usize,
+    /// `div` questions have no remainder.
+    pub exact_division: bool,
+    /// Operands of `sum`, `sub`, `mul`, `div` and `mod` may be negative.
+    pub signed: bool,
+    /// Digits of `sum`, `sub` and the first `mul` operand, the second `mul` operand has two.
+    pub digits: Option<u32>,
+    /// Accepted relative error of estimates in percent.
+    pub estimate_error: f64,
+    /// First and last year of weekday dates.
+    pub years: (i32, i32),
+    /// Units of conversion questions, defaults and those from a units file.
+    pub units: Vec<Unit>,
+    /// Currency display of money questions.
+    pub locale: Locale,
+    /// Highest rule complexity of numeric sequences, see `Rule::complexity`.
+    pub sequence_complexity: u32,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> GeneratorConfig {
+        GeneratorConfig {
+            simplified_fractions: false,
+            decimal_places: 1,
+            percent_tolerance: Tolerance::Round(1),
+            root_tolerance: Tolerance::Round(1),
+            missing_operands: false,
+            expression: ExpressionConfig::default(),
+            chain_steps: 4,
+            exact_division: false,
+            signed: false,
+            digits: None,
+            estimate_error: 10.0,
+            years: (1900, 2099),
+            units: default_units(),
+            locale: Locale::Us,
+            sequence_complexity: 3,
+        }
+    }
+}
+
+pub fn random_question(typ: &str, config: &GeneratorConfig) -> Option<Box<dyn Question>> {
+    let mut rng = rand::thread_rng();
+    let question: Box<dyn Question> = match typ {
+        "sum" | "sub" | "mul" => {
+            let (a, b) = match config.digits {
+                Some(digits) => (
+                    random_with_digits(digits),
+                    random_with_digits(if typ == "mul" { 2 } else { digits }),
+                ),
+                None if typ == "mul" => (rng.gen_range(0..25), rng.gen_range(0..25)),
+                None => (rng.gen_range(0..100), rng.gen_range(0..100)),
+            };
+            let a = a * random_sign(config.signed);
+            let b = b * random_sign(config.signed);
+            match typ {
+                "sum" => Box::new(Sum { a, b }),
+                "sub" => Box::new(Sub { a, b }),
+                _ => Box::new(Mul { a, b }),
+            }
+        }
+        "div" if config.exact_division => {
+            let b = rng.gen_range(1..10) * random_sign(config.signed);
+            Box::new(Div {
+                a: b * rng.gen_range(1..10) * random_sign(config.signed),
+                b,
+            })
+        }
+        "div" => Box::new(Div {
+            a: rng.gen_range(1..20) * random_sign(config.signed),
+            b: rng.gen_range(1..10) * random_sign(config.signed),
+        }),
+        "divrem" => Box::new(DivRem {
+            a: rng.gen_range(1..100),
+            b: rng.gen_range(2..10),
+        }),
+        "mod" => Box::new(Mod {
+            a: rng.gen_range(1..20) * random_sign(config.signed),
+            b: rng.gen_range(1..10) * random_sign(config.signed),
+        }),
+        "percent" => Box::new(PercentProblem {
+            variant: random_percent_variant(),
+            tolerance: config.percent_tolerance,
+        }),
+        "missing" => Box::new(abstract_seq_missing()),
+        "fraction" => Box::new(FractionOp {
+            a: random_fraction(),
+            b: random_fraction(),
+            operator: *Operator::ALL.choose(&mut rng).unwrap(),
+            simplified_only: config.simplified_fractions,
+        }),
+        "simplify" => {
+            let value = random_fraction().simplified();
+            let factor = rng.gen_range(2..6);
+            Box::new(FractionSimplify {
+                value: Fraction {
+                    numerator: value.numerator * factor,
+                    denominator: value.denominator * factor,
+                },
+            })
+        }
+        "decimal" => Box::new(random_decimal_op(config.decimal_places)),
+        "expression" => Box::new(random_expression(&config.expression)),
+        "chain" => Box::new(random_chain(config.chain_steps)),
+        "power" => Box::new(random_power()),
+        "root" => Box::new(random_root(config.root_tolerance)),
+        "estimate" => Box::new(random_estimate(config.estimate_error)),
+        "base" => Box::new(random_base_conversion()),
+        "bitwise" => Box::new(random_bitwise()),
+        "gcd" => Box::new(random_gcd()),
+        "lcm" => Box::new(random_lcm()),
+        "prime" => Box::new(random_prime()),
+        "divisible" => Box::new(random_divisible()),
+        "factor" => Box::new(random_factorization()),
+        "weekday" => Box::new(random_weekday(config.years)),
+        "clock" => Box::new(random_clock_problem()),
+        "unit" => Box::new(random_conversion(&config.units)?),
+        "money" => Box::new(random_money_problem(config.locale)),
+        "sequence" => Box::new(random_sequence(config.sequence_complexity)),
+        _ => return None,
+    };
+    if config.missing_operands {
+        if let Some(fact) = question.fact() {
+            let hidden = if rng.gen_bool(0.5) {
+                Operand::Left
+            } else {
+                Operand::Right
+            };
+            return Some(Box::new(MissingOperand { fact, hidden }));
+        }
+    }
+    Some(question)
+}
+
+fn random_sign(signed: bool) -> i64 {
+    if signed && rand::thread_rng().gen_bool(0.5) {
+        -1
+    } else {
+        1
+    }
+}
+
+// `digits` from 1 to `MAX_DIGITS`
+fn random_with_digits(digits: u32) -> i64 {
+    rand::thread_rng().gen_range(10i64.pow(digits - 1)..10i64.pow(digits))
+}
+
+// small denominators, sometimes improper to train mixed numbers
+fn random_fraction() -> Fraction {
+    let mut rng = rand::thread_rng();
+    let denominator = rng.gen_range(2..13);
+    Fraction {
+        numerator: rng.gen_range(1..denominator * 2),
+        denominator,
+    }
+}
+
+fn random_decimal(max_whole: i64, scale: u32) -> Decimal {
+    let mut rng = rand::thread_rng();
+    let factor = 10i64.pow(scale);
+    Decimal {
+        value: rng.gen_range(1..max_whole * factor),
+        scale,
+    }
+    .normalized()
+}
+
+fn random_decimal_op(scale: u32) -> DecimalOp {
+    let mut rng = rand::thread_rng();
+    let operator = *Operator::ALL.choose(&mut rng).unwrap();
+    match operator {
+        Operator::Add | Operator::Sub => {
+            let a = random_decimal(100, scale);
+            let b = random_decimal(100, rng.gen_range(0..scale + 1));
+            let (a, b) = if a < b { (b, a) } else { (a, b) };
+            DecimalOp { a, b, operator }
+        }
+        Operator::Mul => DecimalOp {
+            a: random_decimal(100, scale),
+            b: random_decimal(10, scale),
+            operator,
+        },
+        // the dividend is built from the quotient so the answer is exact
+        Operator::Div => {
+            let quotient = random_decimal(100, scale);
+            let b = random_decimal(10, scale);
+            DecimalOp {
+                a: quotient.apply(Operator::Mul, &b).unwrap_or(quotient),
+                b,
+                operator,
+            }
+        }
+    }
+}
+
+fn random_percent_variant() -> PercentVariant {
+    let mut rng = rand::thread_rng();
+    // round percents keep `whole` and `rate` answers reasonable
+    let percent = *[5.0, 10.0, 12.5, 15.0, 20.0, 25.0, 40.0, 50.0, 75.0]
+        .choose(&mut rng)
+        .unwrap();
+    let whole = rng.gen_range(1..100) as f64 * 8.0;
+    match rng.gen_range(0..5) {
+        0 => PercentVariant::Part {
+            whole: rng.gen_range(1..1000) as f64,
+            percent: rng.gen_range(1..100) as f64,
+        },
+        1 => PercentVariant::Whole {
+            part: whole * percent / 100.0,
+            percent,
+        },
+        2 => PercentVariant::Rate {
+            part: whole * percent / 100.0,
+            whole,
+        },
+        3 => {
+            let from = rng.gen_range(1..50) as f64 * 10.0;
+            let change = percent * if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+            PercentVariant::Change {
+                from,
+                to: from + from * change / 100.0,
+            }
+        }
+        _ => PercentVariant::Discounts {
+            price: rng.gen_range(1..100) as f64 * 10.0,
+            discounts: (0..rng.gen_range(2..4))
+                .map(|_| rng.gen_range(1..10) as f64 * 5.0)
+                .collect(),
+        },
+    }
+}
+
+fn abstract_seq_missing() -> Missing {
+    let mut rng = rand::thread_rng();
+    let mut items = all_combinations();
+    items.shuffle(&mut rng);
+    let length = items.len();
+    let solution = items[rng.gen_range(0..length)];
+    let mut options: Vec<SeqItem> = vec![];
+    let options_count = 4;
+    let right_position = rng.gen_range(0..options_count);
+    loop {
+        if options.len() == right_position {
+            options.push(solution);
+        }
+        if options.len() == options_count {
+            break;
+        }
+        let candidate = items[rng.gen_range(0..length)];
+        if !options.contains(&candidate) {
+            options.push(candidate);
+        }
+    }
+    Missing {
+        items,
+        options,
+        solution,
+    }
+}
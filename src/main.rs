@@ -1,47 +1,140 @@
 mod abstract_sequence;
+mod anki;
 mod arithmetic;
+mod bits;
+mod calendar;
+mod chain;
+mod clock;
+mod decimal;
+mod deck;
+mod estimation;
+mod expression;
+mod fraction;
+mod generator;
 mod input;
+mod mistakes;
+mod money;
+mod number_theory;
 mod percentage;
+mod power;
+mod repetition;
+mod sequence;
+mod serialization;
 mod stats;
 mod store;
 mod task;
+mod units;
+mod weakness;
 
 mod tasks_pipe;
 use core::time;
 use std::{
     collections::HashSet,
     fmt::format,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use crate::stats::calculate_average_time_millis;
-use abstract_sequence::{all_combinations, Missing, SeqItem};
 use anyhow::{anyhow, Ok, Result};
-use arithmetic::{Div, Mod, Mul, Sub, Sum};
+use arithmetic::Fact;
 use clap::Parser;
-use percentage::Percent;
-use rand::{seq::SliceRandom, Rng};
+use deck::Deck;
+use expression::ExpressionConfig;
+use generator::{random_question, GeneratorConfig, GENERATED_TYPES};
+use percentage::Tolerance;
+use rand::seq::SliceRandom;
 use rusqlite::Connection;
 use stats::{calculate_total_pos_neg, StatsConfig};
 use store::stats as store_stats;
-use task::Question;
-use tasks_pipe::{run, run_with_stats, run_without_steps, PipeMod};
+use task::{Grade, Question};
+use tasks_pipe::{run, run_with_stats_and_steps, PipeMod};
 use uuid::Uuid;
 
+const DATABASE_PATH: &str = "data/stats.db";
+
+struct Attempt {
+    stats: store_stats::Stats,
+    patterns: Vec<String>,
+    fact: Option<Fact>,
+}
+
 #[derive(Parser)]
 struct Args {
     count: u32,
     exersise: String,
     pipe_mod: Option<String>,
     stats_config: Option<String>,
+    /// How many days back `mistakes` looks for wrong answers
+    #[arg(long, default_value_t = 7)]
+    days: u32,
+    /// TOML or CSV file with cards for `deck`, the target TOML file for `import`
+    #[arg(long)]
+    deck: Option<String>,
+    /// Anki plain text or CSV export for `import`
+    #[arg(long)]
+    anki: Option<String>,
+    /// Let `import` replace an existing `--deck` file
+    #[arg(long)]
+    force: bool,
+    /// Accept fraction answers only in lowest terms
+    #[arg(long)]
+    simplified: bool,
+    /// Decimal places of `decimal` operands, at most 6
+    #[arg(long, default_value_t = 1)]
+    places: u32,
+    /// Accepted error of `percent` answers: `exact`, `round:N` or `relative:P`
+    #[arg(long, default_value = "round:1")]
+    tolerance: String,
+    /// Accepted error of approximate `root` answers: `exact`, `round:N` or `relative:P`
+    #[arg(long, default_value = "round:1")]
+    root_tolerance: String,
+    /// Hide an operand of arithmetic questions, e.g. `? + 7 = 15`
+    #[arg(long)]
+    inverse: bool,
+    /// Operators of `expression` questions
+    #[arg(long, default_value = "+-*/")]
+    operators: String,
+    /// Operand count of `expression` questions
+    #[arg(long, default_value_t = 4)]
+    operands: usize,
+    /// Allow parentheses in `expression` questions
+    #[arg(long)]
+    parentheses: bool,
+    /// Steps of `chain` questions
+    #[arg(long, default_value_t = 4)]
+    steps: usize,
+    /// Generate only `div` questions without a remainder
+    #[arg(long)]
+    exact: bool,
+    /// Negative operands in arithmetic questions
+    #[arg(long)]
+    signed: bool,
+    /// Digits of big-number `sum`, `sub` and `mul` operands, from 1 to 16
+    #[arg(long)]
+    digits: Option<u32>,
+    /// Accepted relative error of `estimate` answers in percent
+    #[arg(long, default_value_t = 10.0)]
+    error: f64,
+    /// Year range of `weekday` dates, e.g. `1900-2099`
+    #[arg(long, default_value = "1900-2099")]
+    years: String,
+    /// TOML file with `[[unit]]` entries added to the units of `unit` questions
+    #[arg(long)]
+    units: Option<String>,
+    /// Currency display of `money` questions: `us`, `uk`, `de` or `fr`
+    #[arg(long, default_value = "us")]
+    locale: String,
+    /// Highest rule complexity of `sequence` questions from 1 to 3
+    #[arg(long, default_value_t = 3)]
+    complexity: u32,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let mut rng = rand::thread_rng();
     let mut questions: Vec<Box<dyn Question>> = Vec::new();
-    let types: HashSet<&str> = vec!["sum", "sub", "mul", "div", "mod", "percent", "missing"]
+    let types: HashSet<&str> = GENERATED_TYPES
         .into_iter()
+        .chain(["review", "weak", "mistakes", "deck", "import"])
         .collect();
     let modes: HashSet<&str> = vec!["right", "skip"].into_iter().collect();
 
@@ -58,59 +151,126 @@ fn main() -> Result<()> {
         let message = format!("unknown mod `{}`", &pipe_mod);
         return Err(anyhow!(message));
     }
+    if args.places > decimal::MAX_PLACES {
+        let message = format!(
+            "Decimal places must be from 0 to {}, got `{}`",
+            decimal::MAX_PLACES,
+            args.places
+        );
+        return Err(anyhow!(message));
+    }
+    if !(1..=sequence::MAX_COMPLEXITY).contains(&args.complexity) {
+        let message = format!(
+            "Sequence complexity must be from 1 to {}, got `{}`",
+            sequence::MAX_COMPLEXITY,
+            args.complexity
+        );
+        return Err(anyhow!(message));
+    }
+    if args.error.is_nan() || args.error <= 0.0 {
+        let message = format!("Estimate error must be above 0, got `{}`", args.error);
+        return Err(anyhow!(message));
+    }
+    if let Some(digits) = args.digits {
+        if !(1..=generator::MAX_DIGITS).contains(&digits) {
+            let message = format!(
+                "Digits must be from 1 to {}, got `{}`",
+                generator::MAX_DIGITS,
+                digits
+            );
+            return Err(anyhow!(message));
+        }
+    }
     // todo handle incorrect stats config options
     let stats_configs = match args.stats_config {
         Some(opts) => Some(parse_config_stat_options(opts)),
         None => None,
     };
 
-    for _ in 1..&args.count + 1 {
-        if typ == "sum" {
-            let value = Sum {
-                a: rng.gen_range(0..100),
-                b: rng.gen_range(0..100),
-            };
-            questions.push(Box::new(value));
-        }
-        if typ == "sub" {
-            let value = Sub {
-                a: rng.gen_range(0..100),
-                b: rng.gen_range(0..100),
-            };
-            questions.push(Box::new(value));
+    let generator_config = GeneratorConfig {
+        simplified_fractions: args.simplified,
+        decimal_places: args.places,
+        percent_tolerance: Tolerance::parse(&args.tolerance)?,
+        root_tolerance: Tolerance::parse(&args.root_tolerance)?,
+        missing_operands: args.inverse,
+        expression: ExpressionConfig {
+            operators: expression::parse_operators(&args.operators)?,
+            operands: args.operands,
+            parentheses: args.parentheses,
+        },
+        chain_steps: args.steps,
+        exact_division: args.exact,
+        signed: args.signed,
+        digits: args.digits,
+        estimate_error: args.error,
+        years: calendar::parse_years(&args.years)?,
+        units: match &args.units {
+            Some(path) => units::load_units(path)?,
+            None => units::default_units(),
+        },
+        locale: money::Locale::parse(&args.locale)?,
+        sequence_complexity: args.complexity,
+    };
+
+    match typ {
+        "review" => {
+            let connection = Connection::open(DATABASE_PATH)?;
+            questions = repetition::due_questions(&connection, current_time_millis(), args.count)?;
+            let _ = connection.close();
+            if questions.is_empty() {
+                println!("Nothing to review");
+                return Ok(());
+            }
         }
-        if typ == "mul" {
-            let value = Mul {
-                a: rng.gen_range(0..25),
-                b: rng.gen_range(0..25),
-            };
-            questions.push(Box::new(value));
+        "weak" => {
+            let connection = Connection::open(DATABASE_PATH)?;
+            questions = weakness::weak_questions(&connection, args.count, &generator_config)?;
+            let _ = connection.close();
+            if questions.is_empty() {
+                println!("Not enough history to find weak spots");
+                return Ok(());
+            }
         }
-        if typ == "div" {
-            let value = Div {
-                a: rng.gen_range(1..20),
-                b: rng.gen_range(1..10),
-            };
-            questions.push(Box::new(value));
+        "mistakes" => {
+            let connection = Connection::open(DATABASE_PATH)?;
+            let since_millis = mistakes::since_days_ago(current_time_millis(), args.days);
+            questions = mistakes::mistaken_questions(&connection, since_millis, args.count)?;
+            let _ = connection.close();
+            if questions.is_empty() {
+                println!("No mistakes in the last {} days", args.days);
+                return Ok(());
+            }
         }
-        if typ == "mod" {
-            let value = Mod {
-                a: rng.gen_range(1..20),
-                b: rng.gen_range(1..10),
-            };
-            questions.push(Box::new(value));
+        "import" => {
+            let source = args
+                .anki
+                .as_ref()
+                .ok_or(anyhow!("Anki export is required, use `--anki <path>`"))?;
+            let target = args
+                .deck
+                .as_ref()
+                .ok_or(anyhow!("target deck is required, use `--deck <path>`"))?;
+            let deck = anki::import(source, target, args.force)?;
+            println!("Imported {} cards to `{}`", deck.cards.len(), target);
+            return Ok(());
         }
-        if typ == "percent" {
-            let value = Percent {
-                full: rng.gen_range(1..1000) as f64,
-                percent: rng.gen_range(1..100) as f64,
-                precision: 1,
-            };
-            questions.push(Box::new(value));
+        "deck" => {
+            let path = args
+                .deck
+                .as_ref()
+                .ok_or(anyhow!("deck file is required, use `--deck <path>`"))?;
+            let mut cards = Deck::load(path)?.cards;
+            cards.shuffle(&mut rand::thread_rng());
+            for index in 0..args.count as usize {
+                questions.push(Box::new(cards[index % cards.len()].clone()));
+            }
         }
-        if typ == "missing" {
-            let value = abstract_seq_missing();
-            questions.push(Box::new(value));
+        _ => {
+            for _ in 0..args.count {
+                let question = random_question(typ, &generator_config)
+                    .ok_or(anyhow!(format!("unknown type `{}`", typ)))?;
+                questions.push(question);
+            }
         }
     }
     let pipe_mod = match pipe_mod.as_str() {
@@ -123,14 +283,40 @@ fn main() -> Result<()> {
         percentage: opts.contains("percentage"),
     });
 
+    // answers are saved only when the session uses the database anyway
+    let uses_database =
+        typ == "review" || typ == "weak" || typ == "mistakes" || stats_config.is_some();
+    let mut attempts: Vec<Attempt> = vec![];
+    let mut step_started = Instant::now();
+    let on_step_end = |question: &(dyn Question + 'static), grade: &Grade| {
+        attempts.push(Attempt {
+            stats: store_stats::Stats {
+                id: Uuid::new_v4().to_string(),
+                question_type: String::from(question.question_type()),
+                formatted_body: question.body(),
+                is_answer_right: grade.is_correct(),
+                time_millis: step_started.elapsed().as_millis() as i64,
+                created_at_millis: current_time_millis(),
+                question: question.to_stored().to_json().ok(),
+                resolved_at_millis: None,
+                error: grade.error,
+                score: Some(grade.score),
+            },
+            patterns: question.patterns(),
+            fact: question.fact(),
+        });
+        step_started = Instant::now();
+    };
+
     match stats_config {
         Some(value) => {
-            let stats = run_with_stats(
+            let stats = run_with_stats_and_steps(
                 &questions,
                 &pipe_mod,
                 &mut std::io::stdin().lock(),
                 &mut std::io::stdout(),
                 &value,
+                on_step_end,
             )?;
             if stats.times_millis.is_some() {
                 let average = calculate_average_time_millis(stats.times_millis.unwrap());
@@ -144,61 +330,64 @@ fn main() -> Result<()> {
                     pos_neg.positive + pos_neg.negative
                 );
             }
-            let connection = Connection::open("data/stats.db")?;
-
-            store_stats::create_table_if_not_exist(&connection)?;
-            let stats = store_stats::Stats {
-                id: Uuid::new_v4().to_string(),
-                question_type: String::from("sum"),
-                formatted_body: String::from("1 + 1 = ?"),
-                is_answer_right: false,
-                time_millis: 1200,
-                created_at_millis: 12121212,
-            };
-            store_stats::insert_or_replace(&connection, stats)?;
-            let result = store_stats::select_all(&connection)?;
-            let _ = connection.close();
-            print_stats(&result);
+            // the rate already tells everything without partial credit
+            if let Some(scores) = stats.scores {
+                if scores.iter().any(|score| *score > 0.0 && *score < 1.0) {
+                    let total: f64 = scores.iter().sum();
+                    println!("Score: {:.2} / {}", total, scores.len());
+                }
+            }
         }
         None => {
-            run_without_steps(
+            run(
                 &questions,
                 &pipe_mod,
                 &mut std::io::stdin().lock(),
                 &mut std::io::stdout(),
+                |_| (),
+                on_step_end,
             )?;
         }
     }
 
+    if uses_database && !attempts.is_empty() {
+        let connection = Connection::open(DATABASE_PATH)?;
+        if typ == "mistakes" {
+            let answered = attempts
+                .iter()
+                .filter_map(|attempt| {
+                    let question = attempt.stats.question.clone()?;
+                    Some((question, attempt.stats.is_answer_right))
+                })
+                .collect();
+            mistakes::resolve_answered(&connection, answered, current_time_millis())?;
+        }
+        save_attempts(&connection, attempts)?;
+        let _ = connection.close();
+    }
+
     Ok(())
 }
 
-fn abstract_seq_missing() -> Missing {
-    let mut rng = rand::thread_rng();
-    let mut items = all_combinations();
-    items.shuffle(&mut rng);
-    let length = items.len();
-    let solution = items[rng.gen_range(0..length)];
-    let mut options: Vec<SeqItem> = vec![];
-    let options_count = 4;
-    let right_position = rng.gen_range(0..options_count);
-    loop {
-        if options.len() == right_position {
-            options.push(solution);
-        }
-        if options.len() == options_count {
-            break;
-        }
-        let candidate = items[rng.gen_range(0..length)];
-        if !options.contains(&candidate) {
-            options.push(candidate);
+fn save_attempts(connection: &Connection, attempts: Vec<Attempt>) -> Result<()> {
+    store_stats::create_table_if_not_exist(connection)?;
+    let mut facts: Vec<(Fact, bool)> = vec![];
+    for attempt in attempts {
+        if let Some(fact) = attempt.fact {
+            facts.push((fact, attempt.stats.is_answer_right));
         }
+        let id = attempt.stats.id.clone();
+        store_stats::insert_or_replace(connection, attempt.stats)?;
+        store_stats::insert_patterns(connection, &id, &attempt.patterns)?;
     }
-    return Missing {
-        items,
-        options,
-        solution,
-    };
+    repetition::update_schedules(connection, facts, current_time_millis())
+}
+
+fn current_time_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
 }
 
 fn parse_config_stat_options(opts: String) -> HashSet<String> {
@@ -209,9 +398,3 @@ fn parse_config_stat_options(opts: String) -> HashSet<String> {
     }
     vector.into_iter().collect()
 }
-
-fn print_stats(items: &Vec<store_stats::Stats>) {
-    for item in items {
-        println!("{:?}", item);
-    }
-}
@@ -0,0 +1,123 @@
+use crate::{
+    repetition::{merge_attempts, DAY_MILLIS},
+    serialization::StoredQuestion,
+    store::stats as store_stats,
+    task::Question,
+};
+use anyhow::{Ok, Result};
+use rusqlite::Connection;
+
+pub fn since_days_ago(now_millis: i64, days: u32) -> i64 {
+    now_millis - days as i64 * DAY_MILLIS
+}
+
+/// Questions answered wrongly since `since_millis` and not resolved yet,
+/// each question once even if it was failed several times.
+/// Stored questions in a format this version can not read are skipped.
+pub fn mistaken_questions(
+    connection: &Connection,
+    since_millis: i64,
+    count: u32,
+) -> Result<Vec<Box<dyn Question>>> {
+    store_stats::create_table_if_not_exist(connection)?;
+    let mut seen: Vec<String> = vec![];
+    let mut questions: Vec<Box<dyn Question>> = vec![];
+    for item in store_stats::select_mistakes(connection, since_millis)? {
+        if questions.len() == count as usize {
+            break;
+        }
+        let question = match item.question {
+            Some(question) if !seen.contains(&question) => question,
+            _ => continue,
+        };
+        if let Result::Ok(stored) = StoredQuestion::from_json(&question) {
+            questions.push(stored.into_question());
+        }
+        seen.push(question);
+    }
+    Ok(questions)
+}
+
+/// Marks mistakes resolved for serialized questions which were answered right
+/// on every attempt in the session.
+pub fn resolve_answered(
+    connection: &Connection,
+    attempts: Vec<(String, bool)>,
+    now_millis: i64,
+) -> Result<()> {
+    store_stats::create_table_if_not_exist(connection)?;
+    for (question, is_answer_right) in merge_attempts(attempts) {
+        if is_answer_right {
+            store_stats::resolve_mistakes(connection, &question, now_millis)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn mistakes_replayed_until_resolved() -> Result<()> {
+    use crate::{arithmetic::Mul, store::stats::Stats};
+
+    let connection = Connection::open_in_memory()?;
+    store_stats::create_table_if_not_exist(&connection)?;
+    let question = Mul { a: 7, b: 8 }.to_stored().to_json()?;
+    for (id, created_at_millis) in [("1", 1000), ("2", 2000)] {
+        let stats = Stats {
+            id: String::from(id),
+            question_type: String::from("mul"),
+            formatted_body: String::from("7 * 8 = ?"),
+            is_answer_right: false,
+            time_millis: 1200,
+            created_at_millis,
+            question: Some(question.clone()),
+            resolved_at_millis: None,
+            error: None,
+            score: None,
+        };
+        store_stats::insert_or_replace(&connection, stats)?;
+    }
+    let questions = mistaken_questions(&connection, 0, 10)?;
+    assert_eq!(questions.len(), 1);
+    assert_eq!(questions[0].body(), "7 * 8 = ?");
+
+    resolve_answered(
+        &connection,
+        vec![(question.clone(), false), (question.clone(), true)],
+        3000,
+    )?;
+    assert_eq!(mistaken_questions(&connection, 0, 10)?.len(), 1);
+    resolve_answered(&connection, vec![(question, true)], 3000)?;
+    assert!(mistaken_questions(&connection, 0, 10)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn mistakes_skip_unreadable_questions() -> Result<()> {
+    use crate::{arithmetic::Mul, store::stats::Stats};
+
+    let connection = Connection::open_in_memory()?;
+    store_stats::create_table_if_not_exist(&connection)?;
+    let questions = [
+        Mul { a: 7, b: 8 }.to_stored().to_json()?,
+        String::from(r#"{"type":"pow","a":7,"b":8}"#),
+    ];
+    for (id, question) in questions.into_iter().enumerate() {
+        let stats = Stats {
+            id: id.to_string(),
+            question_type: String::from("mul"),
+            formatted_body: String::from("7 * 8 = ?"),
+            is_answer_right: false,
+            time_millis: 1200,
+            created_at_millis: 1000 + id as i64,
+            question: Some(question),
+            resolved_at_millis: None,
+            error: None,
+            score: None,
+        };
+        store_stats::insert_or_replace(&connection, stats)?;
+    }
+    let questions = mistaken_questions(&connection, 0, 10)?;
+    assert_eq!(questions.len(), 1);
+    assert_eq!(questions[0].body(), "7 * 8 = ?");
+    Ok(())
+}
@@ -0,0 +1,264 @@
+use crate::{serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+/// How amounts are shown, answers are accepted in any of them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// `$1,234.50`
+    Us,
+    /// `£1,234.50`
+    Uk,
+    /// `1.234,50 €`
+    De,
+    /// `1 234,50 €`
+    Fr,
+}
+
+/// Amounts are in cents, answers are rounded to whole cents.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant", rename_all = "lowercase")]
+pub enum MoneyVariant {
+    Total {
+        prices: Vec<i64>,
+    },
+    /// change due when paying the total of `prices` with `paid`
+    Change {
+        prices: Vec<i64>,
+        paid: i64,
+    },
+    Tip {
+        amount: i64,
+        percent: i64,
+    },
+    Tax {
+        amount: i64,
+        percent: i64,
+    },
+    /// share of each of `people`
+    Split {
+        amount: i64,
+        people: i64,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct MoneyProblem {
+    pub variant: MoneyVariant,
+    pub locale: Locale,
+}
+
+impl Locale {
+    pub fn parse(value: &str) -> Result<Locale> {
+        match value.trim().to_lowercase().as_str() {
+            "us" => Ok(Locale::Us),
+            "uk" => Ok(Locale::Uk),
+            "de" => Ok(Locale::De),
+            "fr" => Ok(Locale::Fr),
+            _ => Err(anyhow!(format!("unknown locale `{}`", value))),
+        }
+    }
+
+    pub fn format(&self, cents: i64) -> String {
+        let (group, decimal) = match self {
+            Locale::Us | Locale::Uk => (',', '.'),
+            Locale::De => ('.', ','),
+            Locale::Fr => (' ', ','),
+        };
+        let sign = if cents < 0 { "-" } else { "" };
+        let whole = (cents.abs() / 100).to_string();
+        let mut grouped = String::new();
+        for (index, digit) in whole.chars().enumerate() {
+            if index > 0 && (whole.len() - index).is_multiple_of(3) {
+                grouped.push(group);
+            }
+            grouped.push(digit);
+        }
+        let number = format!("{}{}{:02}", grouped, decimal, cents.abs() % 100);
+        match self {
+            Locale::Us => format!("{}${}", sign, number),
+            Locale::Uk => format!("{}£{}", sign, number),
+            Locale::De | Locale::Fr => format!("{}{} €", sign, number),
+        }
+    }
+}
+
+/// Parses an amount into cents, with or without a currency sign.
+/// The last `.` or `,` followed by one or two digits is the decimal separator,
+/// other separators group thousands, e.g. `1,234.50`, `1.234,5` or `12`.
+pub fn parse_money(value: &str) -> Result<i64> {
+    let error = || format!("Input is not an amount of money `{}`", value.trim());
+    let number: String = value
+        .chars()
+        .filter(|symbol| !matches!(symbol, '$' | '£' | '€' | '\'') && !symbol.is_whitespace())
+        .collect();
+    if number.is_empty()
+        || !number
+            .chars()
+            .all(|symbol| symbol.is_ascii_digit() || symbol == '.' || symbol == ',')
+    {
+        return Err(anyhow!(error()));
+    }
+    let (whole, fraction) = match number.rfind(['.', ',']) {
+        Some(index) if (2..=3).contains(&(number.len() - index)) => {
+            (&number[..index], &number[index + 1..])
+        }
+        _ => (number.as_str(), ""),
+    };
+    let whole: String = whole
+        .chars()
+        .filter(|symbol| symbol.is_ascii_digit())
+        .collect();
+    let whole: i64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().with_context(error)?
+    };
+    let fraction: i64 = match fraction.len() {
+        0 => 0,
+        1 => fraction.parse::<i64>().with_context(error)? * 10,
+        _ => fraction.parse().with_context(error)?,
+    };
+    whole
+        .checked_mul(100)
+        .and_then(|cents| cents.checked_add(fraction))
+        .ok_or_else(|| anyhow!(error()))
+}
+
+// half a cent and more rounds up
+fn percent_of(amount: i64, percent: i64) -> i64 {
+    (amount * percent + 50).div_euclid(100)
+}
+
+impl MoneyVariant {
+    pub fn solution(&self) -> i64 {
+        match self {
+            MoneyVariant::Total { prices } => prices.iter().sum(),
+            MoneyVariant::Change { prices, paid } => paid - prices.iter().sum::<i64>(),
+            MoneyVariant::Tip { amount, percent } | MoneyVariant::Tax { amount, percent } => {
+                amount + percent_of(*amount, *percent)
+            }
+            MoneyVariant::Split { amount, people } => (2 * amount + people) / (2 * people),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            MoneyVariant::Total { .. } => "total",
+            MoneyVariant::Change { .. } => "change",
+            MoneyVariant::Tip { .. } => "tip",
+            MoneyVariant::Tax { .. } => "tax",
+            MoneyVariant::Split { .. } => "split",
+        }
+    }
+}
+
+impl Question for MoneyProblem {
+    fn body(&self) -> String {
+        let format = |cents: &i64| self.locale.format(*cents);
+        let sum = |prices: &Vec<i64>| prices.iter().map(format).collect::<Vec<_>>().join(" + ");
+        match &self.variant {
+            MoneyVariant::Total { prices } => format!("{} = ?", sum(prices)),
+            MoneyVariant::Change { prices, paid } => {
+                format!("{} paid for {}, change = ?", format(paid), sum(prices))
+            }
+            MoneyVariant::Tip { amount, percent } => {
+                format!("{}, total with {} % tip = ?", format(amount), percent)
+            }
+            MoneyVariant::Tax { amount, percent } => {
+                format!("{}, total with {} % tax = ?", format(amount), percent)
+            }
+            MoneyVariant::Split { amount, people } => {
+                format!("{} split {} ways = ?", format(amount), people)
+            }
+        }
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(parse_money(answer)? == self.variant.solution())
+    }
+
+    fn question_type(&self) -> &'static str {
+        "money"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("money:{}", self.variant.name()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Money(self.clone())
+    }
+}
+
+pub fn random_money_problem(locale: Locale) -> MoneyProblem {
+    let mut rng = rand::thread_rng();
+    let count = rng.gen_range(2..5);
+    // at most four prices below 50, so a 200 note always covers the total
+    let prices: Vec<i64> = (0..count).map(|_| rng.gen_range(10..5000)).collect();
+    let variant = match rng.gen_range(0..5) {
+        0 => MoneyVariant::Total { prices },
+        1 => {
+            let total: i64 = prices.iter().sum();
+            // the next banknote above the total
+            let paid = *[500, 1000, 2000, 5000, 10000, 20000]
+                .iter()
+                .find(|note| **note > total)
+                .unwrap();
+            MoneyVariant::Change { prices, paid }
+        }
+        2 => MoneyVariant::Tip {
+            amount: rng.gen_range(1000..20000),
+            percent: *[10, 15, 18, 20].choose(&mut rng).unwrap(),
+        },
+        3 => MoneyVariant::Tax {
+            amount: rng.gen_range(100..20000),
+            percent: *[5, 7, 8, 10, 19, 20].choose(&mut rng).unwrap(),
+        },
+        _ => MoneyVariant::Split {
+            amount: rng.gen_range(2000..50000),
+            people: rng.gen_range(2..9),
+        },
+    };
+    MoneyProblem { variant, locale }
+}
+
+#[test]
+fn money_format_and_parse() -> Result<()> {
+    assert_eq!(Locale::Us.format(123450), "$1,234.50");
+    assert_eq!(Locale::Uk.format(5), "£0.05");
+    assert_eq!(Locale::De.format(123450), "1.234,50 €");
+    assert_eq!(Locale::Fr.format(12345067), "123 450,67 €");
+    assert_eq!(parse_money("$1,234.50")?, 123450);
+    assert_eq!(parse_money("1.234,5 €")?, 123450);
+    assert_eq!(parse_money("1 234,50")?, 123450);
+    assert_eq!(parse_money("1,234")?, 123400);
+    assert_eq!(parse_money("12")?, 1200);
+    assert_eq!(parse_money(".5")?, 50);
+    assert!(parse_money("kek").is_err());
+    assert!(parse_money("").is_err());
+    assert!(Locale::parse("XX").is_err());
+    assert_eq!(Locale::parse("DE")?, Locale::De);
+    assert_eq!(
+        MoneyVariant::Split {
+            amount: 10000,
+            people: 3
+        }
+        .solution(),
+        3333
+    );
+    assert_eq!(
+        MoneyVariant::Tip {
+            amount: 4850,
+            percent: 15
+        }
+        .solution(),
+        5578
+    );
+    Ok(())
+}
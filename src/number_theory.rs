@@ -0,0 +1,261 @@
+use crate::{
+    arithmetic::convert_to_i64, fraction::gcd, serialization::StoredQuestion, task::Question,
+};
+use anyhow::{anyhow, Context, Ok, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Gcd {
+    pub a: i64,
+    pub b: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Lcm {
+    pub a: i64,
+    pub b: i64,
+}
+
+/// Answered `yes` or `no`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Prime {
+    pub value: i64,
+}
+
+/// Answered `yes` or `no`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Divisible {
+    pub value: i64,
+    pub divisor: i64,
+}
+
+/// Prime factors in any order, e.g. `2*2*3*5` or `2^2 * 3 * 5` for `60`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Factorization {
+    pub value: i64,
+}
+
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+pub fn is_prime(value: i64) -> bool {
+    value >= 2 && (2..).take_while(|i| i * i <= value).all(|i| value % i != 0)
+}
+
+/// Prime factors in ascending order with repetitions.
+pub fn prime_factors(value: i64) -> Vec<i64> {
+    let mut factors = vec![];
+    let mut rest = value;
+    let mut divisor = 2;
+    while divisor * divisor <= rest {
+        while rest % divisor == 0 {
+            factors.push(divisor);
+            rest /= divisor;
+        }
+        divisor += 1;
+    }
+    if rest > 1 {
+        factors.push(rest);
+    }
+    factors
+}
+
+/// Parses `2*2*3`, `2^2 * 3`, `2 x 2 x 3` or `2 2 3` into sorted factors.
+pub fn parse_factors(value: &str) -> Result<Vec<i64>> {
+    let error = || format!("Input is not a factorization `{}`", value.trim());
+    let normalized = value.replace(['*', 'x', '×', '·'], " ");
+    let mut factors = vec![];
+    for item in normalized.split_whitespace() {
+        let (factor, power) = item.split_once('^').unwrap_or((item, "1"));
+        let factor: i64 = factor.parse().with_context(error)?;
+        let power: usize = power.parse().with_context(error)?;
+        // no `i64` has a larger power of a prime
+        if power > 63 {
+            return Err(anyhow!(error()));
+        }
+        factors.extend(std::iter::repeat_n(factor, power));
+    }
+    if factors.is_empty() {
+        return Err(anyhow!(error()));
+    }
+    factors.sort();
+    Ok(factors)
+}
+
+pub fn parse_yes_no(value: &str) -> Result<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "y" | "yes" | "true" => Ok(true),
+        "n" | "no" | "false" => Ok(false),
+        _ => Err(anyhow!(format!(
+            "Input is not yes or no `{}`",
+            value.trim()
+        ))),
+    }
+}
+
+impl Question for Gcd {
+    fn body(&self) -> String {
+        format!("gcd {} {} = ?", self.a, self.b)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(convert_to_i64(answer)? == gcd(self.a, self.b))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "gcd"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Gcd(*self)
+    }
+}
+
+impl Question for Lcm {
+    fn body(&self) -> String {
+        format!("lcm {} {} = ?", self.a, self.b)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(convert_to_i64(answer)? == lcm(self.a, self.b))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "lcm"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Lcm(*self)
+    }
+}
+
+impl Question for Prime {
+    fn body(&self) -> String {
+        format!("is {} prime? (y/n)", self.value)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(parse_yes_no(answer)? == is_prime(self.value))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "prime"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Prime(*self)
+    }
+}
+
+impl Question for Divisible {
+    fn body(&self) -> String {
+        format!("is {} divisible by {}? (y/n)", self.value, self.divisor)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let solution = self.divisor != 0 && self.value % self.divisor == 0;
+        Ok(parse_yes_no(answer)? == solution)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "divisible"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("divisible:{}", self.divisor),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Divisible(*self)
+    }
+}
+
+impl Question for Factorization {
+    fn body(&self) -> String {
+        format!("factor {} = ?", self.value)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(parse_factors(answer)? == prime_factors(self.value))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "factor"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Factor(*self)
+    }
+}
+
+// a common factor keeps most answers above one
+pub fn random_gcd() -> Gcd {
+    let mut rng = rand::thread_rng();
+    let common = rng.gen_range(1..13);
+    Gcd {
+        a: common * rng.gen_range(1..13),
+        b: common * rng.gen_range(1..13),
+    }
+}
+
+pub fn random_lcm() -> Lcm {
+    let mut rng = rand::thread_rng();
+    Lcm {
+        a: rng.gen_range(2..16),
+        b: rng.gen_range(2..16),
+    }
+}
+
+// about half of the values are prime, otherwise most would be composite
+pub fn random_prime() -> Prime {
+    let mut rng = rand::thread_rng();
+    let want_prime = rng.gen_bool(0.5);
+    loop {
+        let value = rng.gen_range(2..200);
+        if is_prime(value) == want_prime {
+            return Prime { value };
+        }
+    }
+}
+
+pub fn random_divisible() -> Divisible {
+    let mut rng = rand::thread_rng();
+    let divisor = rng.gen_range(2..13);
+    let value = if rng.gen_bool(0.5) {
+        divisor * rng.gen_range(2..100)
+    } else {
+        rng.gen_range(10..1000)
+    };
+    Divisible { value, divisor }
+}
+
+pub fn random_factorization() -> Factorization {
+    let mut rng = rand::thread_rng();
+    loop {
+        let value = rng.gen_range(4..500);
+        if !is_prime(value) {
+            return Factorization { value };
+        }
+    }
+}
+
+#[test]
+fn factors_as_multisets() -> Result<()> {
+    assert_eq!(prime_factors(60), vec![2, 2, 3, 5]);
+    assert_eq!(parse_factors("2*2*3*5")?, vec![2, 2, 3, 5]);
+    assert_eq!(parse_factors("5 * 3 * 2^2")?, vec![2, 2, 3, 5]);
+    assert_eq!(parse_factors("2 x 3")?, vec![2, 3]);
+    assert!(parse_factors("2^").is_err());
+    assert!(parse_factors("").is_err());
+    assert_eq!(lcm(4, 6), 12);
+    assert!(is_prime(97) && !is_prime(91) && !is_prime(1));
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use crate::task::Question;
-use anyhow::{Context, Ok, Result};
+use crate::{serialization::StoredQuestion, task::Question};
+use anyhow::{anyhow, Context, Ok, Result};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq)]
+// absolute error below which two reals are the same number
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Percent {
     pub full: f64,
     pub percent: f64,
@@ -23,6 +27,145 @@ impl Question for Percent {
         let solution = (self.full * self.percent) / 100.0;
         Ok(approx_equal(answer, solution, self.precision))
     }
+
+    fn question_type(&self) -> &'static str {
+        "percent"
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Percent(self.clone())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "mode", content = "value", rename_all = "lowercase")]
+pub enum Tolerance {
+    Exact,
+    /// Both numbers rounded to the given decimal places are equal.
+    Round(u8),
+    /// Allowed error in percent of the solution.
+    Relative(f64),
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant", rename_all = "lowercase")]
+pub enum PercentVariant {
+    /// `percent % of whole = ?`
+    Part { whole: f64, percent: f64 },
+    /// `part is percent % of ?`
+    Whole { part: f64, percent: f64 },
+    /// `part of whole = ? %`
+    Rate { part: f64, whole: f64 },
+    /// change from one value to another in percent, negative for a decrease
+    Change { from: f64, to: f64 },
+    /// price after applying every discount in turn
+    Discounts { price: f64, discounts: Vec<f64> },
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PercentProblem {
+    pub variant: PercentVariant,
+    pub tolerance: Tolerance,
+}
+
+impl Tolerance {
+    /// Parses `exact`, `round:N` and `relative:P`.
+    pub fn parse(value: &str) -> Result<Tolerance> {
+        let error = || format!("unknown tolerance `{}`", value);
+        let (mode, argument) = value.trim().split_once(':').unwrap_or((value.trim(), ""));
+        match mode {
+            "exact" => Ok(Tolerance::Exact),
+            "round" => Ok(Tolerance::Round(argument.parse().with_context(error)?)),
+            "relative" => Ok(Tolerance::Relative(argument.parse().with_context(error)?)),
+            _ => Err(anyhow!(error())),
+        }
+    }
+
+    pub fn matches(&self, answer: f64, solution: f64) -> bool {
+        match self {
+            Tolerance::Exact => (answer - solution).abs() < EPSILON,
+            Tolerance::Round(decimal_places) => {
+                let factor = 10.0f64.powi(*decimal_places as i32);
+                ((answer * factor).round() - (solution * factor).round()).abs() < EPSILON
+            }
+            Tolerance::Relative(percent) => {
+                (answer - solution).abs() <= solution.abs() * percent / 100.0 + EPSILON
+            }
+        }
+    }
+}
+
+impl PercentVariant {
+    pub fn solution(&self) -> f64 {
+        match self {
+            PercentVariant::Part { whole, percent } => whole * percent / 100.0,
+            PercentVariant::Whole { part, percent } => part * 100.0 / percent,
+            PercentVariant::Rate { part, whole } => part * 100.0 / whole,
+            PercentVariant::Change { from, to } => (to - from) * 100.0 / from,
+            PercentVariant::Discounts { price, discounts } => discounts
+                .iter()
+                .fold(*price, |price, discount| price * (100.0 - discount) / 100.0),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PercentVariant::Part { .. } => "part",
+            PercentVariant::Whole { .. } => "whole",
+            PercentVariant::Rate { .. } => "rate",
+            PercentVariant::Change { .. } => "change",
+            PercentVariant::Discounts { .. } => "discounts",
+        }
+    }
+}
+
+impl Question for PercentProblem {
+    fn body(&self) -> String {
+        match &self.variant {
+            PercentVariant::Part { whole, percent } => format!("{} % of {} = ?", percent, whole),
+            PercentVariant::Whole { part, percent } => format!("{} is {} % of ?", part, percent),
+            PercentVariant::Rate { part, whole } => format!("{} of {} = ? %", part, whole),
+            PercentVariant::Change { from, to } => format!("{} -> {} = ? %", from, to),
+            PercentVariant::Discounts { price, discounts } => {
+                let discounts: Vec<String> = discounts
+                    .iter()
+                    .map(|discount| format!(" - {} %", discount))
+                    .collect();
+                format!("{}{} = ?", price, discounts.join(""))
+            }
+        }
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = parse_real(answer)?;
+        Ok(self.tolerance.matches(answer, self.variant.solution()))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "percent"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("percent:{}", self.variant.name()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::PercentProblem(self.clone())
+    }
+}
+
+/// Real number with `.` or `,` as the separator and an optional `%` sign.
+pub fn parse_real(value: &str) -> Result<f64> {
+    let value = value.trim();
+    value
+        .trim_end_matches('%')
+        .trim()
+        .replace(',', ".")
+        .parse()
+        .with_context(|| format!("Input is not a real number `{}`", value))
 }
 
 fn approx_equal(a: f64, b: f64, decimal_places: u8) -> bool {
@@ -31,3 +174,16 @@ fn approx_equal(a: f64, b: f64, decimal_places: u8) -> bool {
     let b = (b * factor).trunc();
     a == b
 }
+
+#[test]
+fn tolerance_modes() -> Result<()> {
+    assert!(Tolerance::parse("exact")?.matches(0.1 + 0.2, 0.3));
+    assert!(!Tolerance::Exact.matches(14.7, 14.76));
+    assert!(Tolerance::parse("round:1")?.matches(14.8, 14.76));
+    assert!(!Tolerance::Round(1).matches(14.7, 14.76));
+    assert!(Tolerance::parse("relative:5")?.matches(95.0, 100.0));
+    assert!(!Tolerance::Relative(5.0).matches(94.0, 100.0));
+    assert!(Tolerance::parse("round").is_err());
+    assert!(Tolerance::parse("kek").is_err());
+    Ok(())
+}
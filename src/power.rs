@@ -0,0 +1,156 @@
+use crate::{
+    arithmetic::{convert_to_i64, grouped},
+    percentage::{parse_real, Tolerance},
+    serialization::StoredQuestion,
+    task::Question,
+};
+use anyhow::{anyhow, Ok, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Power {
+    pub base: i64,
+    pub exponent: u32,
+}
+
+/// Square root, answered approximately unless the value is a perfect square.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Root {
+    pub value: i64,
+    pub tolerance: Tolerance,
+}
+
+impl Power {
+    fn kind(&self) -> &'static str {
+        match (self.base, self.exponent) {
+            (2, _) => "two",
+            (10, _) => "ten",
+            (base, 2) if base % 10 == 5 => "five",
+            (_, 2) => "square",
+            (_, 3) => "cube",
+            _ => "other",
+        }
+    }
+}
+
+impl Question for Power {
+    fn body(&self) -> String {
+        format!("{}^{} = ?", self.base, self.exponent)
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = convert_to_i64(answer)?;
+        let solution = self
+            .base
+            .checked_pow(self.exponent)
+            .ok_or(anyhow!("Overflow"))?;
+        Ok(answer == solution)
+    }
+
+    fn question_type(&self) -> &'static str {
+        "power"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("power:{}", self.kind()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Power(*self)
+    }
+}
+
+impl Root {
+    pub fn is_perfect(&self) -> bool {
+        let root = (self.value as f64).sqrt().round() as i64;
+        root * root == self.value
+    }
+}
+
+impl Question for Root {
+    fn body(&self) -> String {
+        if self.is_perfect() {
+            format!("sqrt {} = ?", grouped(self.value))
+        } else {
+            format!("sqrt {} ~= ?", grouped(self.value))
+        }
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = parse_real(answer)?;
+        let solution = (self.value as f64).sqrt();
+        if self.is_perfect() {
+            return Ok(answer == solution);
+        }
+        Ok(self.tolerance.matches(answer, solution))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "root"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        let kind = if self.is_perfect() {
+            "perfect"
+        } else {
+            "approximate"
+        };
+        vec![String::from(self.question_type()), format!("root:{}", kind)]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Root(*self)
+    }
+}
+
+/// Squares, squares ending in 5, cubes and powers of two and ten.
+pub fn random_power() -> Power {
+    let mut rng = rand::thread_rng();
+    match rng.gen_range(0..5) {
+        0 => Power {
+            base: rng.gen_range(2..31),
+            exponent: 2,
+        },
+        1 => Power {
+            base: rng.gen_range(1..12) * 10 - 5,
+            exponent: 2,
+        },
+        2 => Power {
+            base: rng.gen_range(2..13),
+            exponent: 3,
+        },
+        3 => Power {
+            base: 2,
+            exponent: rng.gen_range(0..17),
+        },
+        _ => Power {
+            base: 10,
+            exponent: rng.gen_range(0..10),
+        },
+    }
+}
+
+pub fn random_root(tolerance: Tolerance) -> Root {
+    let mut rng = rand::thread_rng();
+    let value = if rng.gen_bool(0.5) {
+        let root: i64 = rng.gen_range(2..31);
+        root * root
+    } else {
+        rng.gen_range(2..500)
+    };
+    Root { value, tolerance }
+}
+
+#[test]
+fn power_patterns() {
+    let kind = |base: i64, exponent: u32| Power { base, exponent }.patterns()[1].clone();
+    assert_eq!(kind(35, 2), "power:five");
+    assert_eq!(kind(12, 2), "power:square");
+    assert_eq!(kind(7, 3), "power:cube");
+    assert_eq!(kind(2, 10), "power:two");
+    assert_eq!(kind(10, 6), "power:ten");
+}
@@ -0,0 +1,152 @@
+use crate::{
+    arithmetic::Fact,
+    store::facts::{self as store_facts, FactSchedule},
+    task::Question,
+};
+use anyhow::{Ok, Result};
+use rusqlite::Connection;
+
+// Leitner boxes: a right answer moves a fact to the next box,
+// a wrong one returns it to the first box where it is due immediately.
+
+pub const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+const INTERVALS_MILLIS: [i64; 6] = [
+    0,
+    DAY_MILLIS,
+    3 * DAY_MILLIS,
+    7 * DAY_MILLIS,
+    14 * DAY_MILLIS,
+    30 * DAY_MILLIS,
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Schedule {
+    pub level: u8,
+    pub due_at_millis: i64,
+}
+
+pub fn next_schedule(
+    previous: Option<Schedule>,
+    is_answer_right: bool,
+    now_millis: i64,
+) -> Schedule {
+    let max_level = (INTERVALS_MILLIS.len() - 1) as u8;
+    let level = match (previous, is_answer_right) {
+        (_, false) => 0,
+        (None, true) => 1,
+        (Some(schedule), true) => (schedule.level + 1).min(max_level),
+    };
+    Schedule {
+        level,
+        due_at_millis: now_millis + INTERVALS_MILLIS[level as usize],
+    }
+}
+
+/// Collapses repeated attempts of the same item within a session:
+/// an item counts as known only if every attempt was right.
+pub fn merge_attempts<T: PartialEq>(attempts: Vec<(T, bool)>) -> Vec<(T, bool)> {
+    let mut result: Vec<(T, bool)> = vec![];
+    for (fact, is_answer_right) in attempts {
+        match result.iter_mut().find(|(item, _)| item == &fact) {
+            Some(item) => item.1 = item.1 && is_answer_right,
+            None => result.push((fact, is_answer_right)),
+        }
+    }
+    result
+}
+
+pub fn update_schedules(
+    connection: &Connection,
+    attempts: Vec<(Fact, bool)>,
+    now_millis: i64,
+) -> Result<()> {
+    store_facts::create_table_if_not_exist(connection)?;
+    for (fact, is_answer_right) in merge_attempts(attempts) {
+        let key = fact.key();
+        let previous = store_facts::select_by_key(connection, &key)?.map(|item| Schedule {
+            level: item.level,
+            due_at_millis: item.due_at_millis,
+        });
+        let schedule = next_schedule(previous, is_answer_right, now_millis);
+        let (a, b) = fact.operands();
+        let item = FactSchedule {
+            key,
+            question_type: String::from(fact.question_type()),
+            a,
+            b,
+            level: schedule.level,
+            due_at_millis: schedule.due_at_millis,
+        };
+        store_facts::insert_or_replace(connection, &item)?;
+    }
+    Ok(())
+}
+
+pub fn due_questions(
+    connection: &Connection,
+    now_millis: i64,
+    count: u32,
+) -> Result<Vec<Box<dyn Question>>> {
+    store_facts::create_table_if_not_exist(connection)?;
+    let mut questions: Vec<Box<dyn Question>> = vec![];
+    for item in store_facts::select_due(connection, now_millis, count)? {
+        let fact = Fact::from_parts(&item.question_type, item.a, item.b)?;
+        questions.push(fact.into_question());
+    }
+    Ok(questions)
+}
+
+#[test]
+fn next_schedule_moves_between_levels() {
+    let first = next_schedule(None, true, 0);
+    assert_eq!(
+        first,
+        Schedule {
+            level: 1,
+            due_at_millis: DAY_MILLIS
+        }
+    );
+    let second = next_schedule(Some(first), true, DAY_MILLIS);
+    assert_eq!(
+        second,
+        Schedule {
+            level: 2,
+            due_at_millis: 4 * DAY_MILLIS
+        }
+    );
+    let failed = next_schedule(Some(second), false, 5 * DAY_MILLIS);
+    assert_eq!(
+        failed,
+        Schedule {
+            level: 0,
+            due_at_millis: 5 * DAY_MILLIS
+        }
+    );
+}
+
+#[test]
+fn next_schedule_keeps_last_level() {
+    let last = Schedule {
+        level: 5,
+        due_at_millis: 0,
+    };
+    assert_eq!(next_schedule(Some(last), true, 0).level, 5);
+}
+
+#[test]
+fn review_returns_only_due_facts() -> Result<()> {
+    use crate::arithmetic::Mul;
+
+    let connection = Connection::open_in_memory()?;
+    let known = Fact::Mul(Mul { a: 2, b: 2 });
+    let unknown = Fact::Mul(Mul { a: 7, b: 8 });
+    update_schedules(
+        &connection,
+        vec![(known, true), (unknown, true), (unknown, false)],
+        1000,
+    )?;
+    let questions = due_questions(&connection, 1000, 10)?;
+    assert_eq!(questions.len(), 1);
+    assert_eq!(questions[0].fact(), Some(unknown));
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use crate::{arithmetic::convert_to_i64, serialization::StoredQuestion, task::Question};
+use anyhow::{Ok, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Highest value of `Rule::complexity`.
+pub const MAX_COMPLEXITY: u32 = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "lowercase")]
+pub enum Rule {
+    /// `2, 5, 8, 11`
+    Arithmetic { start: i64, step: i64 },
+    /// `1, 4, 9, 16` from `start` squared
+    Squares { start: i64 },
+    /// `3, 6, 12, 24`
+    Geometric { start: i64, ratio: i64 },
+    /// `1, 3, 4, 7` where every term is the sum of the two before it
+    Fibonacci { first: i64, second: i64 },
+    /// `10, 13, 11, 14` adding `up` and `down` in turn
+    Alternating { start: i64, up: i64, down: i64 },
+    /// `1, 20, 3, 18, 5` two arithmetic sequences taking turns
+    Interleaved {
+        start: i64,
+        step: i64,
+        other_start: i64,
+        other_step: i64,
+    },
+}
+
+/// Numeric sequence with one term replaced by `?`, the last one asks for the next term.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct NumberSequence {
+    pub rule: Rule,
+    pub length: usize,
+    pub hidden: usize,
+}
+
+impl Rule {
+    /// Term at a zero-based position.
+    pub fn term(&self, index: usize) -> i64 {
+        let n = index as i64;
+        match *self {
+            Rule::Arithmetic { start, step } => start + step * n,
+            Rule::Squares { start } => (start + n) * (start + n),
+            Rule::Geometric { start, ratio } => start * ratio.pow(index as u32),
+            Rule::Fibonacci { first, second } => {
+                let (mut a, mut b) = (first, second);
+                for _ in 0..index {
+                    (a, b) = (b, a + b);
+                }
+                a
+            }
+            Rule::Alternating { start, up, down } => start + (n + 1) / 2 * up - n / 2 * down,
+            Rule::Interleaved {
+                start,
+                step,
+                other_start,
+                other_step,
+            } => {
+                if index.is_multiple_of(2) {
+                    start + step * (n / 2)
+                } else {
+                    other_start + other_step * (n / 2)
+                }
+            }
+        }
+    }
+
+    /// `1` for a single constant step up to `3` for two sequences in one.
+    pub fn complexity(&self) -> u32 {
+        match self {
+            Rule::Arithmetic { .. } | Rule::Squares { .. } => 1,
+            Rule::Geometric { .. } | Rule::Fibonacci { .. } | Rule::Alternating { .. } => 2,
+            Rule::Interleaved { .. } => 3,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Rule::Arithmetic { .. } => "arithmetic",
+            Rule::Squares { .. } => "squares",
+            Rule::Geometric { .. } => "geometric",
+            Rule::Fibonacci { .. } => "fibonacci",
+            Rule::Alternating { .. } => "alternating",
+            Rule::Interleaved { .. } => "interleaved",
+        }
+    }
+}
+
+impl Question for NumberSequence {
+    fn body(&self) -> String {
+        let terms: Vec<String> = (0..self.length)
+            .map(|index| {
+                if index == self.hidden {
+                    String::from("?")
+                } else {
+                    self.rule.term(index).to_string()
+                }
+            })
+            .collect();
+        terms.join(", ")
+    }
+
+    fn check(&self, answer: &String) -> Result<bool> {
+        Ok(convert_to_i64(answer)? == self.rule.term(self.hidden))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "sequence"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("sequence:{}", self.rule.name()),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Sequence(*self)
+    }
+}
+
+/// Rules up to `complexity`, harder rules show more terms.
+pub fn random_sequence(complexity: u32) -> NumberSequence {
+    let mut rng = rand::thread_rng();
+    let rule = loop {
+        let rule = match rng.gen_range(0..6) {
+            0 => Rule::Arithmetic {
+                start: rng.gen_range(-20..50),
+                step: rng.gen_range(-9..12),
+            },
+            1 => Rule::Squares {
+                start: rng.gen_range(1..10),
+            },
+            2 => Rule::Geometric {
+                start: rng.gen_range(1..6),
+                ratio: rng.gen_range(2..5),
+            },
+            3 => Rule::Fibonacci {
+                first: rng.gen_range(1..6),
+                second: rng.gen_range(1..6),
+            },
+            4 => Rule::Alternating {
+                start: rng.gen_range(1..30),
+                up: rng.gen_range(2..10),
+                down: rng.gen_range(1..8),
+            },
+            _ => Rule::Interleaved {
+                start: rng.gen_range(1..20),
+                step: rng.gen_range(1..6),
+                other_start: rng.gen_range(20..50),
+                other_step: -rng.gen_range(1..6),
+            },
+        };
+        // a zero step is a constant, not a sequence
+        let constant = matches!(rule, Rule::Arithmetic { step: 0, .. });
+        if rule.complexity() <= complexity.max(1) && !constant {
+            break rule;
+        }
+    };
+    let length = 4 + rule.complexity() as usize;
+    let hidden = if rng.gen_bool(0.5) {
+        length - 1
+    } else {
+        rng.gen_range(1..length - 1)
+    };
+    NumberSequence {
+        rule,
+        length,
+        hidden,
+    }
+}
+
+#[test]
+fn sequence_terms() {
+    let terms = |rule: Rule| -> Vec<i64> { (0..6).map(|index| rule.term(index)).collect() };
+    assert_eq!(
+        terms(Rule::Fibonacci {
+            first: 1,
+            second: 3
+        }),
+        vec![1, 3, 4, 7, 11, 18]
+    );
+    assert_eq!(
+        terms(Rule::Alternating {
+            start: 10,
+            up: 3,
+            down: 2
+        }),
+        vec![10, 13, 11, 14, 12, 15]
+    );
+    assert_eq!(
+        terms(Rule::Interleaved {
+            start: 1,
+            step: 2,
+            other_start: 20,
+            other_step: -2
+        }),
+        vec![1, 20, 3, 18, 5, 16]
+    );
+    assert_eq!(
+        terms(Rule::Geometric { start: 3, ratio: 2 }),
+        vec![3, 6, 12, 24, 48, 96]
+    );
+}
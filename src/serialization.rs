@@ -0,0 +1,142 @@
+use crate::{
+    abstract_sequence::Missing,
+    arithmetic::{Div, DivRem, MissingOperand, Mod, Mul, Sub, Sum},
+    bits::{BaseConversion, Bitwise},
+    calendar::Weekday,
+    chain::Chain,
+    clock::ClockProblem,
+    decimal::DecimalOp,
+    deck::Card,
+    estimation::Estimate,
+    expression::Expression,
+    fraction::{FractionOp, FractionSimplify},
+    money::MoneyProblem,
+    number_theory::{Divisible, Factorization, Gcd, Lcm, Prime},
+    percentage::{Percent, PercentProblem},
+    power::{Power, Root},
+    sequence::NumberSequence,
+    task::Question,
+    units::Conversion,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Type tag plus parameters of a question, enough to reconstruct it exactly,
+/// e.g. `{"type":"mul","a":7,"b":8}`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StoredQuestion {
+    Sum(Sum),
+    Sub(Sub),
+    Mul(Mul),
+    Div(Div),
+    Mod(Mod),
+    Percent(Percent),
+    Missing(Missing),
+    Card(Card),
+    Fraction(FractionOp),
+    Simplify(FractionSimplify),
+    Decimal(DecimalOp),
+    #[serde(rename = "percent_problem")]
+    PercentProblem(PercentProblem),
+    #[serde(rename = "missing_operand")]
+    MissingOperand(MissingOperand),
+    Expression(Expression),
+    Chain(Chain),
+    DivRem(DivRem),
+    Power(Power),
+    Root(Root),
+    Estimate(Estimate),
+    Base(BaseConversion),
+    Bitwise(Bitwise),
+    Gcd(Gcd),
+    Lcm(Lcm),
+    Prime(Prime),
+    Divisible(Divisible),
+    Factor(Factorization),
+    Weekday(Weekday),
+    Clock(ClockProblem),
+    Unit(Conversion),
+    Money(MoneyProblem),
+    Sequence(NumberSequence),
+}
+
+impl StoredQuestion {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).context("Question can not be serialized")
+    }
+
+    pub fn from_json(value: &str) -> Result<StoredQuestion> {
+        serde_json::from_str(value).with_context(|| format!("Unknown stored question `{}`", value))
+    }
+
+    pub fn into_question(self) -> Box<dyn Question> {
+        match self {
+            StoredQuestion::Sum(value) => Box::new(value),
+            StoredQuestion::Sub(value) => Box::new(value),
+            StoredQuestion::Mul(value) => Box::new(value),
+            StoredQuestion::Div(value) => Box::new(value),
+            StoredQuestion::Mod(value) => Box::new(value),
+            StoredQuestion::Percent(value) => Box::new(value),
+            StoredQuestion::Missing(value) => Box::new(value),
+            StoredQuestion::Card(value) => Box::new(value),
+            StoredQuestion::Fraction(value) => Box::new(value),
+            StoredQuestion::Simplify(value) => Box::new(value),
+            StoredQuestion::Decimal(value) => Box::new(value),
+            StoredQuestion::PercentProblem(value) => Box::new(value),
+            StoredQuestion::MissingOperand(value) => Box::new(value),
+            StoredQuestion::Expression(value) => Box::new(value),
+            StoredQuestion::Chain(value) => Box::new(value),
+            StoredQuestion::DivRem(value) => Box::new(value),
+            StoredQuestion::Power(value) => Box::new(value),
+            StoredQuestion::Root(value) => Box::new(value),
+            StoredQuestion::Estimate(value) => Box::new(value),
+            StoredQuestion::Base(value) => Box::new(value),
+            StoredQuestion::Bitwise(value) => Box::new(value),
+            StoredQuestion::Gcd(value) => Box::new(value),
+            StoredQuestion::Lcm(value) => Box::new(value),
+            StoredQuestion::Prime(value) => Box::new(value),
+            StoredQuestion::Divisible(value) => Box::new(value),
+            StoredQuestion::Factor(value) => Box::new(value),
+            StoredQuestion::Weekday(value) => Box::new(value),
+            StoredQuestion::Clock(value) => Box::new(value),
+            StoredQuestion::Unit(value) => Box::new(value),
+            StoredQuestion::Money(value) => Box::new(value),
+            StoredQuestion::Sequence(value) => Box::new(value),
+        }
+    }
+}
+
+#[test]
+fn stored_question_json() -> Result<()> {
+    let stored = Mul { a: 7, b: 8 }.to_stored();
+    assert_eq!(stored.to_json()?, r#"{"type":"mul","a":7,"b":8}"#);
+    assert_eq!(
+        StoredQuestion::from_json(r#"{"type":"mul","a":7,"b":8}"#)?,
+        stored
+    );
+    assert!(StoredQuestion::from_json(r#"{"type":"pow","a":7,"b":8}"#).is_err());
+    Ok(())
+}
+
+#[test]
+fn stored_question_round_trip() -> Result<()> {
+    use crate::generator::{random_question, GeneratorConfig, GENERATED_TYPES};
+
+    let inverse = GeneratorConfig {
+        missing_operands: true,
+        ..GeneratorConfig::default()
+    };
+    for (typ, config) in GENERATED_TYPES
+        .iter()
+        .flat_map(|typ| [(typ, GeneratorConfig::default()), (typ, inverse.clone())])
+    {
+        let question = random_question(typ, &config).unwrap();
+        let json = question.to_stored().to_json()?;
+        let restored = StoredQuestion::from_json(&json)?.into_question();
+        assert_eq!(restored.question_type(), question.question_type());
+        assert_eq!(restored.body(), question.body());
+        assert_eq!(restored.to_stored(), question.to_stored());
+    }
+    Ok(())
+}
@@ -4,10 +4,12 @@ pub struct StatsConfig {
     pub percentage: bool,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq)]
 pub struct CollectedStats {
     pub times_millis: Option<Vec<u128>>,
     pub pos_negs: Option<Vec<bool>>,
+    /// Graded scores of the same answers as `pos_negs`.
+    pub scores: Option<Vec<f64>>,
 }
 
 pub struct PosNeg {
@@ -0,0 +1,86 @@
+use std::result;
+
+use anyhow::{Ok, Result};
+use rusqlite::{Connection, OptionalExtension};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FactSchedule {
+    pub key: String,
+    pub question_type: String,
+    pub a: i64,
+    pub b: i64,
+    pub level: u8,
+    pub due_at_millis: i64,
+}
+
+pub fn create_table_if_not_exist(connection: &Connection) -> Result<()> {
+    let query = "
+      CREATE TABLE IF NOT EXISTS Facts (
+        key TEXT NOT NULL PRIMARY KEY,
+        question_type TEXT NOT NULL,
+        a INTEGER NOT NULL,
+        b INTEGER NOT NULL,
+        level INTEGER NOT NULL,
+        due_at_millis INTEGER NOT NULL
+      )
+    ";
+    connection.execute(query, ())?;
+    Ok(())
+}
+
+pub fn insert_or_replace(connection: &Connection, schedule: &FactSchedule) -> Result<()> {
+    let query = "
+     INSERT OR REPLACE INTO Facts
+     VALUES (?1, ?2, ?3, ?4, ?5, ?6);
+  ";
+    connection.execute(
+        query,
+        (
+            &schedule.key,
+            &schedule.question_type,
+            schedule.a,
+            schedule.b,
+            schedule.level,
+            schedule.due_at_millis,
+        ),
+    )?;
+    Ok(())
+}
+
+pub fn select_by_key(connection: &Connection, key: &str) -> Result<Option<FactSchedule>> {
+    let query = "SELECT key, question_type, a, b, level, due_at_millis FROM Facts WHERE key = ?1";
+    let item = connection.query_row(query, [key], map_row).optional()?;
+    Ok(item)
+}
+
+/// Facts due at `now_millis`, the most overdue first.
+pub fn select_due(
+    connection: &Connection,
+    now_millis: i64,
+    limit: u32,
+) -> Result<Vec<FactSchedule>> {
+    let query = "
+      SELECT key, question_type, a, b, level, due_at_millis FROM Facts
+      WHERE due_at_millis <= ?1
+      ORDER BY due_at_millis, level
+      LIMIT ?2
+    ";
+    let mut items = connection.prepare(query)?;
+    let items = items.query_map((now_millis, limit), map_row)?;
+    let mut result: Vec<FactSchedule> = vec![];
+    for item in items {
+        result.push(item?);
+    }
+    Ok(result)
+}
+
+fn map_row(row: &rusqlite::Row) -> rusqlite::Result<FactSchedule> {
+    result::Result::Ok(FactSchedule {
+        key: row.get(0)?,
+        question_type: row.get(1)?,
+        a: row.get(2)?,
+        b: row.get(3)?,
+        level: row.get(4)?,
+        due_at_millis: row.get(5)?,
+    })
+}
@@ -4,7 +4,17 @@ use anyhow::{Ok, Result};
 use rusqlite::Connection;
 use uuid::Uuid;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq)]
+pub struct PatternStats {
+    pub pattern: String,
+    pub total: i64,
+    pub right: i64,
+    pub average_time_millis: f64,
+    /// Average graded score, rows written before grades count as `0` or `1`.
+    pub average_score: f64,
+}
+
+#[derive(Debug, PartialEq)]
 pub struct Stats {
     pub id: String,
     pub question_type: String,
@@ -12,6 +22,14 @@ pub struct Stats {
     pub is_answer_right: bool,
     pub time_millis: i64,
     pub created_at_millis: i64,
+    /// Serialized question, see `StoredQuestion`; absent in rows written before it was stored.
+    pub question: Option<String>,
+    /// Set once a wrong answer is answered right in a mistakes session.
+    pub resolved_at_millis: Option<i64>,
+    /// Relative error of the answer for questions scored by closeness.
+    pub error: Option<f64>,
+    /// Graded score from `0` to `1`, absent in rows written before grades.
+    pub score: Option<f64>,
 }
 
 pub fn create_table_if_not_exist(connection: &Connection) -> Result<()> {
@@ -22,7 +40,23 @@ pub fn create_table_if_not_exist(connection: &Connection) -> Result<()> {
         formatted_body TEXT NOT NULL,
         is_answer_right INTEGER,
         time_millis INTEGER,
-        created_at_millis INTEGER
+        created_at_millis INTEGER,
+        question TEXT,
+        resolved_at_millis INTEGER,
+        error REAL,
+        score REAL
+      )
+    ";
+    connection.execute(query, ())?;
+    add_column_if_not_exist(connection, "question", "TEXT")?;
+    add_column_if_not_exist(connection, "resolved_at_millis", "INTEGER")?;
+    add_column_if_not_exist(connection, "error", "REAL")?;
+    add_column_if_not_exist(connection, "score", "REAL")?;
+    let query = "
+      CREATE TABLE IF NOT EXISTS StatsPatterns (
+        stats_id TEXT NOT NULL,
+        pattern TEXT NOT NULL,
+        PRIMARY KEY (stats_id, pattern)
       )
     ";
     connection.execute(query, ())?;
@@ -30,39 +64,230 @@ pub fn create_table_if_not_exist(connection: &Connection) -> Result<()> {
 }
 
 pub fn insert_or_replace(connection: &Connection, stats: Stats) -> Result<()> {
-    let query = format!(
-        "
-     INSERT OR REPLACE INTO Stats 
-     VALUES ('{}', '{}', '{}', {}, {}, {});
-  ",
-        stats.id,
-        stats.question_type,
-        stats.formatted_body,
-        if stats.is_answer_right { 1 } else { 0 },
-        stats.time_millis,
-        stats.created_at_millis
-    );
-
-    connection.execute(&query, ())?;
+    // parameters instead of formatting, serialized questions contain quotes
+    let query = "
+     INSERT OR REPLACE INTO Stats
+     (id, question_type, formatted_body, is_answer_right, time_millis, created_at_millis, question, resolved_at_millis, error, score)
+     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10);
+  ";
+    connection.execute(
+        query,
+        (
+            stats.id,
+            stats.question_type,
+            stats.formatted_body,
+            stats.is_answer_right,
+            stats.time_millis,
+            stats.created_at_millis,
+            stats.question,
+            stats.resolved_at_millis,
+            stats.error,
+            stats.score,
+        ),
+    )?;
     Ok(())
 }
 
+#[cfg(test)]
 pub fn select_all(connection: &Connection) -> Result<Vec<Stats>> {
-    let query = "SELECT id, question_type, formatted_body, is_answer_right, time_millis, created_at_millis FROM Stats";
+    let query = "SELECT id, question_type, formatted_body, is_answer_right, time_millis, created_at_millis, question, resolved_at_millis, error, score FROM Stats";
     let mut items = connection.prepare(query)?;
-    let items = items.query_map([], |row| {
-        result::Result::Ok(Stats {
-            id: row.get(0)?,
-            question_type: row.get(1)?,
-            formatted_body: row.get(2)?,
-            is_answer_right: row.get(3)?,
-            time_millis: row.get(4)?,
-            created_at_millis: row.get(5)?,
-        })
-    })?;
+    let items = items.query_map([], map_row)?;
     let mut reeult: Vec<Stats> = vec![];
     for item in items {
         reeult.push(item?);
     }
     return Ok(reeult);
 }
+
+/// Unresolved wrong answers with a stored question since `since_millis`, the latest first.
+pub fn select_mistakes(connection: &Connection, since_millis: i64) -> Result<Vec<Stats>> {
+    let query = "
+      SELECT id, question_type, formatted_body, is_answer_right, time_millis, created_at_millis, question, resolved_at_millis, error, score
+      FROM Stats
+      WHERE is_answer_right = 0 AND resolved_at_millis IS NULL
+        AND question IS NOT NULL AND created_at_millis >= ?1
+      ORDER BY created_at_millis DESC
+    ";
+    let mut items = connection.prepare(query)?;
+    let items = items.query_map([since_millis], map_row)?;
+    let mut result: Vec<Stats> = vec![];
+    for item in items {
+        result.push(item?);
+    }
+    Ok(result)
+}
+
+pub fn resolve_mistakes(
+    connection: &Connection,
+    question: &str,
+    resolved_at_millis: i64,
+) -> Result<()> {
+    let query = "
+      UPDATE Stats SET resolved_at_millis = ?1
+      WHERE question = ?2 AND is_answer_right = 0 AND resolved_at_millis IS NULL
+    ";
+    connection.execute(query, (resolved_at_millis, question))?;
+    Ok(())
+}
+
+pub fn insert_patterns(connection: &Connection, stats_id: &str, patterns: &[String]) -> Result<()> {
+    let query = "INSERT OR REPLACE INTO StatsPatterns VALUES (?1, ?2)";
+    for pattern in patterns {
+        connection.execute(query, (stats_id, pattern))?;
+    }
+    Ok(())
+}
+
+pub fn select_pattern_stats(connection: &Connection) -> Result<Vec<PatternStats>> {
+    let query = "
+      SELECT p.pattern, COUNT(*), SUM(s.is_answer_right), AVG(s.time_millis),
+        AVG(COALESCE(s.score, s.is_answer_right))
+      FROM StatsPatterns p JOIN Stats s ON s.id = p.stats_id
+      GROUP BY p.pattern
+    ";
+    let mut items = connection.prepare(query)?;
+    let items = items.query_map([], |row| {
+        result::Result::Ok(PatternStats {
+            pattern: row.get(0)?,
+            total: row.get(1)?,
+            right: row.get(2)?,
+            average_time_millis: row.get(3)?,
+            average_score: row.get(4)?,
+        })
+    })?;
+    let mut result: Vec<PatternStats> = vec![];
+    for item in items {
+        result.push(item?);
+    }
+    Ok(result)
+}
+
+fn map_row(row: &rusqlite::Row) -> rusqlite::Result<Stats> {
+    result::Result::Ok(Stats {
+        id: row.get(0)?,
+        question_type: row.get(1)?,
+        formatted_body: row.get(2)?,
+        is_answer_right: row.get(3)?,
+        time_millis: row.get(4)?,
+        created_at_millis: row.get(5)?,
+        question: row.get(6)?,
+        resolved_at_millis: row.get(7)?,
+        error: row.get(8)?,
+        score: row.get(9)?,
+    })
+}
+
+// tables created by older versions miss the columns added later
+fn add_column_if_not_exist(connection: &Connection, column: &str, definition: &str) -> Result<()> {
+    let mut columns = connection.prepare("SELECT name FROM pragma_table_info('Stats')")?;
+    let columns = columns.query_map([], |row| row.get::<_, String>(0))?;
+    for name in columns {
+        if name? == column {
+            return Ok(());
+        }
+    }
+    let query = format!("ALTER TABLE Stats ADD COLUMN {} {}", column, definition);
+    connection.execute(&query, ())?;
+    Ok(())
+}
+
+#[test]
+fn stored_question_survives_database() -> Result<()> {
+    use crate::{arithmetic::Mul, serialization::StoredQuestion, task::Question};
+
+    let connection = Connection::open_in_memory()?;
+    create_table_if_not_exist(&connection)?;
+    let question = Mul { a: 7, b: 8 };
+    let stats = Stats {
+        id: String::from("1"),
+        question_type: String::from(question.question_type()),
+        formatted_body: question.body(),
+        is_answer_right: false,
+        time_millis: 1200,
+        created_at_millis: 1000,
+        question: Some(question.to_stored().to_json()?),
+        resolved_at_millis: None,
+        error: None,
+        score: None,
+    };
+    insert_or_replace(&connection, stats)?;
+    let items = select_all(&connection)?;
+    let restored = StoredQuestion::from_json(items[0].question.as_ref().unwrap())?;
+    assert_eq!(restored, StoredQuestion::Mul(question));
+    Ok(())
+}
+
+#[test]
+fn question_column_added_to_old_table() -> Result<()> {
+    let connection = Connection::open_in_memory()?;
+    connection.execute(
+        "CREATE TABLE Stats (id TEXT NOT NULL PRIMARY KEY, question_type TEXT NOT NULL, formatted_body TEXT NOT NULL, is_answer_right INTEGER, time_millis INTEGER, created_at_millis INTEGER)",
+        (),
+    )?;
+    connection.execute(
+        "INSERT INTO Stats VALUES ('1', 'sum', '1 + 1 = ?', 1, 1200, 1000)",
+        (),
+    )?;
+    create_table_if_not_exist(&connection)?;
+    let items = select_all(&connection)?;
+    assert_eq!(items[0].question, None);
+    assert_eq!(items[0].resolved_at_millis, None);
+    assert_eq!(items[0].error, None);
+    assert_eq!(items[0].score, None);
+    Ok(())
+}
+
+#[test]
+fn mistakes_resolved_once() -> Result<()> {
+    let connection = Connection::open_in_memory()?;
+    create_table_if_not_exist(&connection)?;
+    let stats = |id: &str, is_answer_right: bool, created_at_millis: i64| Stats {
+        id: String::from(id),
+        question_type: String::from("sum"),
+        formatted_body: String::from("1 + 1 = ?"),
+        is_answer_right,
+        time_millis: 1200,
+        created_at_millis,
+        question: Some(String::from(r#"{"type":"sum","a":1,"b":1}"#)),
+        resolved_at_millis: None,
+        error: None,
+        score: None,
+    };
+    insert_or_replace(&connection, stats("1", false, 1000))?;
+    insert_or_replace(&connection, stats("2", true, 2000))?;
+    insert_or_replace(&connection, stats("3", false, 3000))?;
+    let ids: Vec<String> = select_mistakes(&connection, 2000)?
+        .into_iter()
+        .map(|item| item.id)
+        .collect();
+    assert_eq!(ids, vec!["3"]);
+    resolve_mistakes(&connection, r#"{"type":"sum","a":1,"b":1}"#, 4000)?;
+    assert!(select_mistakes(&connection, 0)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn pattern_stats_average_score() -> Result<()> {
+    let connection = Connection::open_in_memory()?;
+    create_table_if_not_exist(&connection)?;
+    for (id, is_answer_right, score) in [("1", true, None), ("2", false, Some(0.5))] {
+        let stats = Stats {
+            id: String::from(id),
+            question_type: String::from("estimate"),
+            formatted_body: String::from("487 * 23 ~= ?"),
+            is_answer_right,
+            time_millis: 1000,
+            created_at_millis: 1000,
+            question: None,
+            resolved_at_millis: None,
+            error: score.map(|_| 0.15),
+            score,
+        };
+        insert_or_replace(&connection, stats)?;
+        insert_patterns(&connection, id, &[String::from("estimate")])?;
+    }
+    let items = select_pattern_stats(&connection)?;
+    assert_eq!((items[0].right, items[0].average_score), (1, 0.75));
+    Ok(())
+}
@@ -1,6 +1,70 @@
-use anyhow::Result;
+use crate::{arithmetic::Fact, serialization::StoredQuestion};
+use anyhow::{Ok, Result};
+use std::fmt;
+
+/// Outcome of an answer, `score` is from `0` for wrong to `1` for right,
+/// anything between is partial credit.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Grade {
+    pub score: f64,
+    /// Relative error of the answer for questions scored by closeness, `0.1` is 10 %.
+    pub error: Option<f64>,
+}
+
+impl Grade {
+    pub fn from_bool(correct: bool) -> Grade {
+        Grade {
+            score: if correct { 1.0 } else { 0.0 },
+            error: None,
+        }
+    }
+
+    pub fn is_correct(&self) -> bool {
+        self.score >= 1.0
+    }
+}
+
+/// `true` and `false` as before grades, `partial 0.5` for partial credit.
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_correct() {
+            write!(f, "true")?;
+        } else if self.score > 0.0 {
+            write!(f, "partial {:.2}", self.score)?;
+        } else {
+            write!(f, "false")?;
+        }
+        if let Some(error) = self.error {
+            write!(f, ", error {:.1} %", error * 100.0)?;
+        }
+        fmt::Result::Ok(())
+    }
+}
 
 pub trait Question {
     fn body(&self) -> String;
     fn check(&self, answer: &String) -> Result<bool>;
+    fn question_type(&self) -> &'static str;
+    fn to_stored(&self) -> StoredQuestion;
+
+    /// Tags like `mul:7` or `sub:borrow` used to find weak spots in the history.
+    /// The first tag is always the question type.
+    fn patterns(&self) -> Vec<String> {
+        vec![String::from(self.question_type())]
+    }
+
+    /// Graded outcome, questions with partial credit or an error size override it.
+    fn grade(&self, answer: &String) -> Result<Grade> {
+        Ok(Grade::from_bool(self.check(answer)?))
+    }
+
+    /// Lines shown one at a time before the body, each cleared after Enter.
+    fn stages(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Arithmetic fact behind the question, if it is tracked for review.
+    fn fact(&self) -> Option<Fact> {
+        None
+    }
 }
@@ -1,10 +1,24 @@
 use crate::{
     abstract_sequence::{all_combinations, Form, Missing, SeqItem, Size},
-    arithmetic::{Div, Mod, Mul, Sub, Sum},
+    arithmetic::{Div, DivRem, Fact, MissingOperand, Mod, Mul, Operand, Operator, Sub, Sum},
+    bits::{Base, BaseConversion, BitOperator, Bitwise},
+    calendar::Weekday,
+    chain::{Chain, Step},
+    clock::ClockProblem,
+    decimal::{Decimal, DecimalOp},
+    deck::{AnswerKind, Card},
+    estimation::{Estimate, EstimateTarget},
+    expression::{Expr, Expression},
+    fraction::{Fraction, FractionOp, FractionSimplify},
     input::{self, DefferedInput},
-    percentage::Percent,
+    money::{Locale, MoneyProblem, MoneyVariant},
+    number_theory::{Divisible, Factorization, Gcd, Lcm, Prime},
+    percentage::{Percent, PercentProblem, PercentVariant, Tolerance},
+    power::{Power, Root},
+    sequence::{NumberSequence, Rule},
     stats::{CollectedStats, StatsConfig},
-    task::Question,
+    task::{Grade, Question},
+    units::{default_units, Conversion},
 };
 use anyhow::{Ok, Result};
 use std::{
@@ -14,12 +28,16 @@ use std::{
     time::{Duration, Instant},
 };
 
+// moves up over the stage and the echoed Enter and clears them
+const CLEAR_STAGE: &str = "\x1b[2A\x1b[J";
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum PipeMod {
     UntilRight,
     Skip,
 }
 
+#[cfg(test)]
 pub fn run_without_steps<Q: Question + ?Sized>(
     questions: &Vec<Box<Q>>,
     pipe_mod: &PipeMod,
@@ -29,16 +47,29 @@ pub fn run_without_steps<Q: Question + ?Sized>(
     run(questions, pipe_mod, reader, writer, |_| (), |_, _| ())
 }
 
+#[cfg(test)]
 pub fn run_with_stats<Q: Question + ?Sized>(
     questions: &Vec<Box<Q>>,
     pipe_mod: &PipeMod,
-    mut reader: impl BufRead,
-    mut writer: impl Write,
+    reader: impl BufRead,
+    writer: impl Write,
+    stats_config: &StatsConfig,
+) -> Result<CollectedStats> {
+    run_with_stats_and_steps(questions, pipe_mod, reader, writer, stats_config, |_, _| ())
+}
+
+pub fn run_with_stats_and_steps<Q: Question + ?Sized, FEnd: FnMut(&Q, &Grade)>(
+    questions: &Vec<Box<Q>>,
+    pipe_mod: &PipeMod,
+    reader: impl BufRead,
+    writer: impl Write,
     stats_config: &StatsConfig,
+    mut on_step_end: FEnd,
 ) -> Result<CollectedStats> {
     let instant = Instant::now();
     let mut times: Vec<u128> = vec![];
     let mut pos_negs: Vec<bool> = vec![];
+    let mut scores: Vec<f64> = vec![];
 
     run(
         &questions,
@@ -46,31 +77,35 @@ pub fn run_with_stats<Q: Question + ?Sized>(
         reader,
         writer,
         |_| (),
-        |_, answer| {
+        |question, grade| {
+            on_step_end(question, grade);
+            let answer = grade.is_correct();
             if answer && pipe_mod == &PipeMod::UntilRight || pipe_mod == &PipeMod::Skip {
                 if stats_config.time {
                     times.push(instant.elapsed().as_millis())
                 }
                 if stats_config.percentage {
-                    pos_negs.push(answer)
+                    pos_negs.push(answer);
+                    scores.push(grade.score);
                 }
             }
         },
     )?;
     let times = if stats_config.time { Some(times) } else { None };
-    let pos_negs = if stats_config.percentage {
-        Some(pos_negs)
+    let (pos_negs, scores) = if stats_config.percentage {
+        (Some(pos_negs), Some(scores))
     } else {
-        None
+        (None, None)
     };
     Ok(CollectedStats {
         times_millis: times,
         pos_negs: pos_negs,
+        scores,
     })
 }
 
 // todo: move reader and writer to step functiobs?
-pub fn run<Q: Question + ?Sized, FStart: FnMut(&Q) -> (), FEnd: FnMut(&Q, bool) -> ()>(
+pub fn run<Q: Question + ?Sized, FStart: FnMut(&Q) -> (), FEnd: FnMut(&Q, &Grade) -> ()>(
     questions: &Vec<Box<Q>>,
     pipe_mod: &PipeMod,
     mut reader: impl BufRead,
@@ -85,17 +120,22 @@ pub fn run<Q: Question + ?Sized, FStart: FnMut(&Q) -> (), FEnd: FnMut(&Q, bool)
     let length = questions.len();
     loop {
         let question = &questions[index];
+        for stage in question.stages() {
+            writeln!(writer, "{}", stage)?;
+            reader.read_line(&mut String::new())?;
+            write!(writer, "{}", CLEAR_STAGE)?;
+        }
         let body = question.body();
         writeln!(writer, "{}", body)?;
         on_step_start(&question);
         let mut line = String::new();
         reader.read_line(&mut line)?;
-        let result = question.check(&line);
+        let result = question.grade(&line);
         match result {
-            Result::Ok(correct) => {
-                on_step_end(&question, correct);
-                writeln!(writer, "{}", correct)?;
-                index = next_index(index, correct, pipe_mod);
+            Result::Ok(grade) => {
+                on_step_end(&question, &grade);
+                writeln!(writer, "{}", grade)?;
+                index = next_index(index, grade.is_correct(), pipe_mod);
             }
             Err(error) => {
                 writeln!(writer, "{}", error)?;
@@ -178,6 +218,63 @@ fn div_and_mod_0() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn signed_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Sub { a: 7, b: -3 }),
+        Box::new(Sum { a: -7, b: 3 }),
+        Box::new(Mul { a: -4, b: -5 }),
+        Box::new(Div { a: -7, b: 2 }),
+        Box::new(Mod { a: -7, b: 3 }),
+        Box::new(Mod { a: 7, b: -3 }),
+    ];
+    let mut input = "10\n-4\n20\n-4\n2\n1\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"7 - (-3) = ?\ntrue\n-7 + 3 = ?\ntrue\n-4 * (-5) = ?\ntrue\n-7 div 2 = ?\ntrue\n-7 mod 3 = ?\ntrue\n7 mod (-3) = ?\ntrue\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn big_numbers_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Mul { a: 12345, b: 67 }),
+        Box::new(Sum {
+            a: 4_000_000_000,
+            b: -1_234_567,
+        }),
+    ];
+    let mut input = "827 115\n3998765433\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"12 345 * 67 = ?\ntrue\n4 000 000 000 + (-1 234 567) = ?\ntrue\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn div_with_remainder_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(DivRem { a: 17, b: 5 }),
+        Box::new(DivRem { a: 17, b: 5 }),
+        Box::new(DivRem { a: 20, b: 5 }),
+        Box::new(DivRem { a: 17, b: 5 }),
+    ];
+    let mut input = "3 r 2\n3,2\n4\n3\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"17 div 5 = ? r ?\ntrue\n17 div 5 = ? r ?\ntrue\n20 div 5 = ? r ?\ntrue\n17 div 5 = ? r ?\nfalse\n"
+    );
+    Ok(())
+}
+
 #[test]
 fn percents_0() -> Result<()> {
     let questions: Vec<Box<dyn Question>> = vec![
@@ -226,6 +323,467 @@ fn percents_1() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn missing_operands_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(MissingOperand {
+            fact: Fact::Sum(Sum { a: 8, b: 7 }),
+            hidden: Operand::Left,
+        }),
+        Box::new(MissingOperand {
+            fact: Fact::Mul(Mul { a: 7, b: 6 }),
+            hidden: Operand::Left,
+        }),
+        Box::new(MissingOperand {
+            fact: Fact::Div(Div { a: 56, b: 7 }),
+            hidden: Operand::Right,
+        }),
+        Box::new(MissingOperand {
+            fact: Fact::Div(Div { a: 13, b: 4 }),
+            hidden: Operand::Left,
+        }),
+        Box::new(MissingOperand {
+            fact: Fact::Sub(Sub { a: 10, b: 4 }),
+            hidden: Operand::Right,
+        }),
+        Box::new(MissingOperand {
+            fact: Fact::Mod(Mod { a: 7, b: 3 }),
+            hidden: Operand::Right,
+        }),
+    ];
+    let mut input = "8\n6\n7\n12\n6\n0\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"? + 7 = 15\ntrue\n? * 6 = 42\nfalse\n56 div ? = 8\ntrue\n? div 4 = 3\ntrue\n10 - ? = 6\nfalse\n7 mod ? = 1\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn expressions_0() -> Result<()> {
+    let number = Expr::number;
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Expression {
+            root: Expr::binary(
+                Operator::Sub,
+                Expr::binary(
+                    Operator::Add,
+                    number(12),
+                    Expr::binary(Operator::Mul, number(3), number(4)),
+                ),
+                Expr::binary(Operator::Div, number(6), number(2)),
+            ),
+        }),
+        Box::new(Expression {
+            root: Expr::binary(
+                Operator::Mul,
+                Expr::binary(Operator::Add, number(2), number(3)),
+                number(4),
+            ),
+        }),
+    ];
+    let mut input = "21\n14\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"12 + 3 * 4 - 6 div 2 = ?\ntrue\n(2 + 3) * 4 = ?\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn chain_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![Box::new(Chain {
+        start: 7,
+        steps: vec![
+            Step {
+                operator: Operator::Add,
+                operand: 5,
+            },
+            Step {
+                operator: Operator::Mul,
+                operand: 3,
+            },
+            Step {
+                operator: Operator::Sub,
+                operand: 4,
+            },
+        ],
+    })];
+    let mut input = "\n\n\n\n33\n\n\n\n\n32\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::UntilRight, &mut input, &mut output)?;
+    let stages = "start at 7\n\x1b[2A\x1b[J+ 5\n\x1b[2A\x1b[J* 3\n\x1b[2A\x1b[J- 4\n\x1b[2A\x1b[J";
+    assert_eq!(
+        String::from_utf8(output)?,
+        format!("{}= ?\nfalse\n{}= ?\ntrue\n", stages, stages)
+    );
+    Ok(())
+}
+
+#[test]
+fn powers_and_roots_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Power {
+            base: 35,
+            exponent: 2,
+        }),
+        Box::new(Power {
+            base: 2,
+            exponent: 20,
+        }),
+        Box::new(Root {
+            value: 144,
+            tolerance: Tolerance::Round(1),
+        }),
+        Box::new(Root {
+            value: 144,
+            tolerance: Tolerance::Round(1),
+        }),
+        Box::new(Root {
+            value: 50,
+            tolerance: Tolerance::Round(1),
+        }),
+        Box::new(Root {
+            value: 50,
+            tolerance: Tolerance::Round(1),
+        }),
+    ];
+    let mut input = "1225\n1 048 576\n12\n12.04\n7,07\n7\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"35^2 = ?\ntrue\n2^20 = ?\ntrue\nsqrt 144 = ?\ntrue\nsqrt 144 = ?\nfalse\nsqrt 50 ~= ?\ntrue\nsqrt 50 ~= ?\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn estimates_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Estimate {
+            target: EstimateTarget::Product { a: 487, b: 23 },
+            max_error: 10.0,
+        }),
+        Box::new(Estimate {
+            target: EstimateTarget::Root { value: 2000 },
+            max_error: 5.0,
+        }),
+    ];
+    let mut input = "10 000\n40\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    let mut errors: Vec<Option<f64>> = vec![];
+    run(
+        &questions,
+        &PipeMod::Skip,
+        &mut input,
+        &mut output,
+        |_| (),
+        |_, grade| errors.push(grade.error.map(|value| (value * 100.0).round())),
+    )?;
+    assert_eq!(
+        &output,
+        b"487 * 23 ~= ?\npartial 0.93, error 10.7 %\nsqrt 2000 ~= ?\nfalse, error 10.6 %\n"
+    );
+    assert_eq!(errors, vec![Some(11.0), Some(11.0)]);
+    Ok(())
+}
+
+#[test]
+fn bases_and_bitwise_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(BaseConversion {
+            value: 47,
+            from: Base::Hex,
+            to: Base::Bin,
+        }),
+        Box::new(BaseConversion {
+            value: 47,
+            from: Base::Dec,
+            to: Base::Hex,
+        }),
+        Box::new(Bitwise {
+            a: 0b1010,
+            b: 0b0110,
+            operator: BitOperator::Xor,
+        }),
+        Box::new(Bitwise {
+            a: 5,
+            b: 2,
+            operator: BitOperator::Shl,
+        }),
+    ];
+    let mut input = "0b101111\n2F\n0b1100\n0x15\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"0x2f -> bin = ?\ntrue\n47 -> hex = ?\ntrue\n0b1010 ^ 0b110 = ?\ntrue\n5 << 2 = ?\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn number_theory_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Gcd { a: 12, b: 18 }),
+        Box::new(Lcm { a: 4, b: 6 }),
+        Box::new(Prime { value: 91 }),
+        Box::new(Divisible {
+            value: 231,
+            divisor: 7,
+        }),
+        Box::new(Factorization { value: 60 }),
+        Box::new(Factorization { value: 60 }),
+    ];
+    let mut input = "6\n24\nno\ny\n2^2 * 3 * 5\n2*3*5\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"gcd 12 18 = ?\ntrue\nlcm 4 6 = ?\nfalse\nis 91 prime? (y/n)\ntrue\nis 231 divisible by 7? (y/n)\ntrue\nfactor 60 = ?\ntrue\nfactor 60 = ?\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn weekdays_0() -> Result<()> {
+    let date = Weekday {
+        year: 1969,
+        month: 7,
+        day: 20,
+    };
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(date),
+        Box::new(date),
+        Box::new(date),
+        Box::new(date),
+    ];
+    let mut input = "do\nsunday\nSonntag\n7\n0\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        "1969-07-20 = ? (weekday)\nWeekday `do` is ambiguous\n1969-07-20 = ? (weekday)\ntrue\n1969-07-20 = ? (weekday)\ntrue\n1969-07-20 = ? (weekday)\ntrue\n1969-07-20 = ? (weekday)\ntrue\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn clock_problems_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(ClockProblem::Shift {
+            time: 14 * 60 + 45,
+            minutes: 155,
+        }),
+        Box::new(ClockProblem::Shift {
+            time: 30,
+            minutes: -45,
+        }),
+        Box::new(ClockProblem::Difference {
+            from: 22 * 60 + 10,
+            to: 60 + 5,
+        }),
+        Box::new(ClockProblem::Convert { minutes: 155 }),
+        Box::new(ClockProblem::Angle { time: 3 * 60 + 30 }),
+    ];
+    let mut input = "5:20xm\n5:20pm\n23:45\n2h 55m\n155\n2:35\n75\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        "14:45 + 2h 35m = ?\nInput is not a time `5:20xm`\n14:45 + 2h 35m = ?\ntrue\n0:30 - 45m = ?\ntrue\n22:10 -> 1:05 = ? (h:mm)\ntrue\n155 min = ? (h:mm)\nInput is not in hours and minutes `155`\n155 min = ? (h:mm)\ntrue\nangle 3:30 = ?\ntrue\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn unit_conversions_0() -> Result<()> {
+    let units = default_units();
+    let unit = |name: &str| units.iter().find(|unit| unit.name == name).unwrap().clone();
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Conversion {
+            value: 5.0,
+            from: unit("mi"),
+            to: unit("km"),
+            tolerance: Tolerance::Relative(1.0),
+        }),
+        Box::new(Conversion {
+            value: 5.0,
+            from: unit("mi"),
+            to: unit("km"),
+            tolerance: Tolerance::Relative(1.0),
+        }),
+        Box::new(Conversion {
+            value: 1.0,
+            from: unit("MiB"),
+            to: unit("KB"),
+            tolerance: Tolerance::Exact,
+        }),
+        Box::new(Conversion {
+            value: 100.0,
+            from: unit("°F"),
+            to: unit("°C"),
+            tolerance: Tolerance::Round(0),
+        }),
+    ];
+    let mut input = "8 km\n7.5\n1048,576\n38\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        "5 mi -> km = ?\ntrue\n5 mi -> km = ?\nfalse\n1 MiB -> KB = ?\ntrue\n100 °F -> °C = ?\ntrue\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn money_problems_0() -> Result<()> {
+    let problem = |variant: MoneyVariant, locale: Locale| -> Box<dyn Question> {
+        Box::new(MoneyProblem { variant, locale })
+    };
+    let questions: Vec<Box<dyn Question>> = vec![
+        problem(
+            MoneyVariant::Total {
+                prices: vec![349, 1299, 75],
+            },
+            Locale::Us,
+        ),
+        problem(
+            MoneyVariant::Change {
+                prices: vec![349, 1299],
+                paid: 2000,
+            },
+            Locale::Uk,
+        ),
+        problem(
+            MoneyVariant::Tip {
+                amount: 4860,
+                percent: 15,
+            },
+            Locale::De,
+        ),
+        problem(
+            MoneyVariant::Tax {
+                amount: 123450,
+                percent: 20,
+            },
+            Locale::Fr,
+        ),
+        problem(
+            MoneyVariant::Split {
+                amount: 12740,
+                people: 4,
+            },
+            Locale::Us,
+        ),
+    ];
+    let mut input = "$17.23\nkek\n3.52\n55,89 €\n1 481,40\n31.85\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        "$3.49 + $12.99 + $0.75 = ?\ntrue\n£20.00 paid for £3.49 + £12.99, change = ?\nInput is not an amount of money `kek`\n£20.00 paid for £3.49 + £12.99, change = ?\ntrue\n48,60 €, total with 15 % tip = ?\ntrue\n1 234,50 €, total with 20 % tax = ?\ntrue\n$127.40 split 4 ways = ?\ntrue\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn number_sequences_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(NumberSequence {
+            rule: Rule::Arithmetic { start: 2, step: 3 },
+            length: 5,
+            hidden: 4,
+        }),
+        Box::new(NumberSequence {
+            rule: Rule::Squares { start: 1 },
+            length: 5,
+            hidden: 2,
+        }),
+        Box::new(NumberSequence {
+            rule: Rule::Interleaved {
+                start: 1,
+                step: 2,
+                other_start: 20,
+                other_step: -2,
+            },
+            length: 7,
+            hidden: 5,
+        }),
+    ];
+    let mut input = "14\n8\n16\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        "2, 5, 8, 11, ?\ntrue\n1, 4, ?, 16, 25\nfalse\n1, 20, 3, 18, 5, ?, 7\ntrue\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn percent_variants_0() -> Result<()> {
+    let problem = |variant: PercentVariant, tolerance: Tolerance| -> Box<dyn Question> {
+        Box::new(PercentProblem { variant, tolerance })
+    };
+    let questions: Vec<Box<dyn Question>> = vec![
+        problem(
+            PercentVariant::Part {
+                whole: 80.0,
+                percent: 15.0,
+            },
+            Tolerance::Exact,
+        ),
+        problem(
+            PercentVariant::Whole {
+                part: 12.0,
+                percent: 15.0,
+            },
+            Tolerance::Exact,
+        ),
+        problem(
+            PercentVariant::Rate {
+                part: 1.0,
+                whole: 3.0,
+            },
+            Tolerance::Round(1),
+        ),
+        problem(
+            PercentVariant::Change {
+                from: 80.0,
+                to: 60.0,
+            },
+            Tolerance::Exact,
+        ),
+        problem(
+            PercentVariant::Discounts {
+                price: 100.0,
+                discounts: vec![20.0, 10.0],
+            },
+            Tolerance::Relative(1.0),
+        ),
+    ];
+    let mut input = "12
+80
+33,3 %
+-25
+70
+"
+    .as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"15 % of 80 = ?\ntrue\n12 is 15 % of ?\ntrue\n1 of 3 = ? %\ntrue\n80 -> 60 = ? %\ntrue\n100 - 20 % - 10 % = ?\nfalse\n"
+    );
+    Ok(())
+}
+
 #[test]
 fn abstract_seq_missing_0() -> Result<()> {
     let item1 = SeqItem {
@@ -338,6 +896,7 @@ fn mod_skip_with_stats_0() -> Result<()> {
     let expected_stats = CollectedStats {
         times_millis: Some(vec![0, 0]),
         pos_negs: None,
+        scores: None,
     };
     let stats = run_with_stats(
         &questions,
@@ -370,6 +929,7 @@ fn mod_skip_with_stats_1() -> Result<()> {
     let expected_stats = CollectedStats {
         times_millis: Some(vec![50, 100, 150]),
         pos_negs: Some(vec![true, true, false]),
+        scores: Some(vec![1.0, 1.0, 0.0]),
     };
     let stats = run_with_stats(
         &questions,
@@ -402,6 +962,7 @@ fn mod_skip_with_stats_2() -> Result<()> {
     let expected_stats = CollectedStats {
         times_millis: None,
         pos_negs: Some(vec![false, true]),
+        scores: Some(vec![0.0, 1.0]),
     };
     let stats = run_with_stats(
         &questions,
@@ -431,6 +992,7 @@ fn mod_until_right_with_stats_2() -> Result<()> {
     let expected_stats = CollectedStats {
         times_millis: Some(vec![50, 150]),
         pos_negs: Some(vec![true, true]),
+        scores: Some(vec![1.0, 1.0]),
     };
     let stats = run_with_stats(
         &questions,
@@ -446,3 +1008,187 @@ fn mod_until_right_with_stats_2() -> Result<()> {
     assert_eq!(stats, expected_stats);
     Ok(())
 }
+
+#[test]
+fn deck_cards_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Card {
+            prompt: String::from("HTTPS port"),
+            answer: String::from("443"),
+            kind: AnswerKind::Integer,
+            tolerance: None,
+            options: vec![],
+            tags: vec![],
+        }),
+        Box::new(Card {
+            prompt: String::from("sqrt 2"),
+            answer: String::from("1.414"),
+            kind: AnswerKind::Real,
+            tolerance: Some(0.01),
+            options: vec![],
+            tags: vec![],
+        }),
+        Box::new(Card {
+            prompt: String::from("Capital of France"),
+            answer: String::from("Paris"),
+            kind: AnswerKind::Text,
+            tolerance: None,
+            options: vec![],
+            tags: vec![],
+        }),
+    ];
+    let mut input = "443\n1.42\nparis\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"HTTPS port\ntrue\nsqrt 2\ntrue\nCapital of France\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn deck_cards_1() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(Card {
+            prompt: String::from("DNS transport"),
+            answer: String::from("UDP"),
+            kind: AnswerKind::Choice,
+            tolerance: None,
+            options: vec![String::from("TCP"), String::from("UDP")],
+            tags: vec![],
+        }),
+        Box::new(Card {
+            prompt: String::from("sqrt 2"),
+            answer: String::from("1.414"),
+            kind: AnswerKind::Real,
+            tolerance: Some(0.001),
+            options: vec![],
+            tags: vec![],
+        }),
+    ];
+    let mut input = "3\n1\n1.5\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"DNS transport\n1. TCP 2. UDP\nInput is not in range 3\nDNS transport\n1. TCP 2. UDP\nfalse\nsqrt 2\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn fractions_0() -> Result<()> {
+    let half = Fraction {
+        numerator: 1,
+        denominator: 2,
+    };
+    let quarter = Fraction {
+        numerator: 1,
+        denominator: 4,
+    };
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(FractionOp {
+            a: half,
+            b: quarter,
+            operator: Operator::Add,
+            simplified_only: false,
+        }),
+        Box::new(FractionOp {
+            a: half,
+            b: quarter,
+            operator: Operator::Div,
+            simplified_only: false,
+        }),
+        Box::new(FractionOp {
+            a: half,
+            b: half,
+            operator: Operator::Add,
+            simplified_only: true,
+        }),
+    ];
+    let mut input = "6/8\n2\n2/2\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"1/2 + 1/4 = ?\ntrue\n1/2 div 1/4 = ?\ntrue\n1/2 + 1/2 = ?\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn fractions_1() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(FractionOp {
+            a: Fraction {
+                numerator: 3,
+                denominator: 2,
+            },
+            b: Fraction {
+                numerator: 1,
+                denominator: 3,
+            },
+            operator: Operator::Mul,
+            simplified_only: true,
+        }),
+        Box::new(FractionSimplify {
+            value: Fraction {
+                numerator: 9,
+                denominator: 6,
+            },
+        }),
+        Box::new(FractionSimplify {
+            value: Fraction {
+                numerator: 9,
+                denominator: 6,
+            },
+        }),
+    ];
+    let mut input = "1/2\n1 1/2\n6/4\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"1 1/2 * 1/3 = ?\ntrue\nsimplify 9/6 = ?\ntrue\nsimplify 9/6 = ?\nfalse\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn decimals_0() -> Result<()> {
+    let questions: Vec<Box<dyn Question>> = vec![
+        Box::new(DecimalOp {
+            a: Decimal {
+                value: 37,
+                scale: 1,
+            },
+            b: Decimal { value: 4, scale: 1 },
+            operator: Operator::Mul,
+        }),
+        Box::new(DecimalOp {
+            a: Decimal {
+                value: 125,
+                scale: 1,
+            },
+            b: Decimal {
+                value: 786,
+                scale: 2,
+            },
+            operator: Operator::Sub,
+        }),
+        Box::new(DecimalOp {
+            a: Decimal { value: 1, scale: 1 },
+            b: Decimal { value: 2, scale: 1 },
+            operator: Operator::Add,
+        }),
+    ];
+    let mut input = "1,48\n4.640\n0.300000000001\n".as_bytes();
+    let mut output: Vec<u8> = Vec::new();
+    run_without_steps(&questions, &PipeMod::Skip, &mut input, &mut output)?;
+    assert_eq!(
+        &output,
+        b"3.7 * 0.4 = ?\ntrue\n12.5 - 7.86 = ?\ntrue\n0.1 + 0.2 = ?\nfalse\n"
+    );
+    Ok(())
+}
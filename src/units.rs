@@ -0,0 +1,197 @@
+use crate::{
+    percentage::{parse_real, Tolerance},
+    serialization::StoredQuestion,
+    task::Question,
+};
+use anyhow::{anyhow, Context, Ok, Result};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+// name, dimension, factor and offset to the base unit of the dimension
+const DEFAULT_UNITS: [(&str, &str, f64, f64); 31] = [
+    ("m", "length", 1.0, 0.0),
+    ("km", "length", 1000.0, 0.0),
+    ("cm", "length", 0.01, 0.0),
+    ("mm", "length", 0.001, 0.0),
+    ("in", "length", 0.0254, 0.0),
+    ("ft", "length", 0.3048, 0.0),
+    ("yd", "length", 0.9144, 0.0),
+    ("mi", "length", 1609.344, 0.0),
+    ("kg", "mass", 1.0, 0.0),
+    ("g", "mass", 0.001, 0.0),
+    ("t", "mass", 1000.0, 0.0),
+    ("lb", "mass", 0.45359237, 0.0),
+    ("oz", "mass", 0.028349523125, 0.0),
+    ("°C", "temperature", 1.0, 0.0),
+    ("°F", "temperature", 5.0 / 9.0, -160.0 / 9.0),
+    ("K", "temperature", 1.0, -273.15),
+    ("B", "data", 1.0, 0.0),
+    ("KB", "data", 1e3, 0.0),
+    ("MB", "data", 1e6, 0.0),
+    ("GB", "data", 1e9, 0.0),
+    ("KiB", "data", 1024.0, 0.0),
+    ("MiB", "data", 1048576.0, 0.0),
+    ("GiB", "data", 1073741824.0, 0.0),
+    ("bit/s", "bandwidth", 1.0, 0.0),
+    ("kbit/s", "bandwidth", 1e3, 0.0),
+    ("Mbit/s", "bandwidth", 1e6, 0.0),
+    ("Gbit/s", "bandwidth", 1e9, 0.0),
+    ("KB/s", "bandwidth", 8e3, 0.0),
+    ("MB/s", "bandwidth", 8e6, 0.0),
+    ("GB/s", "bandwidth", 8e9, 0.0),
+    ("MiB/s", "bandwidth", 8.0 * 1048576.0, 0.0),
+];
+
+/// A value in this unit is `value * factor + offset` in the base unit of its dimension.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Unit {
+    pub name: String,
+    pub dimension: String,
+    pub factor: f64,
+    #[serde(default)]
+    pub offset: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct UnitTable {
+    #[serde(default, rename = "unit")]
+    units: Vec<Unit>,
+}
+
+/// Converting `value` from one unit to another of the same dimension, e.g. `5 mi -> km = ?`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Conversion {
+    pub value: f64,
+    pub from: Unit,
+    pub to: Unit,
+    pub tolerance: Tolerance,
+}
+
+pub fn default_units() -> Vec<Unit> {
+    DEFAULT_UNITS
+        .iter()
+        .map(|(name, dimension, factor, offset)| Unit {
+            name: String::from(*name),
+            dimension: String::from(*dimension),
+            factor: *factor,
+            offset: *offset,
+        })
+        .collect()
+}
+
+/// Default units extended with the `[[unit]]` entries of a TOML file,
+/// an entry with the name of a default unit replaces it.
+pub fn load_units(path: &str) -> Result<Vec<Unit>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Can not read units `{}`", path))?;
+    extend_units(default_units(), &content)
+}
+
+pub fn extend_units(mut units: Vec<Unit>, content: &str) -> Result<Vec<Unit>> {
+    let table: UnitTable = toml::from_str(content).context("Units are not a valid TOML")?;
+    for unit in table.units {
+        if unit.factor == 0.0 || !unit.factor.is_finite() || !unit.offset.is_finite() {
+            return Err(anyhow!(format!(
+                "Unit `{}` has an invalid factor",
+                unit.name
+            )));
+        }
+        units.retain(|known| known.name != unit.name);
+        units.push(unit);
+    }
+    Ok(units)
+}
+
+impl Conversion {
+    pub fn solution(&self) -> f64 {
+        let base = self.value * self.from.factor + self.from.offset;
+        (base - self.to.offset) / self.to.factor
+    }
+}
+
+impl Question for Conversion {
+    fn body(&self) -> String {
+        format!("{} {} -> {} = ?", self.value, self.from.name, self.to.name)
+    }
+
+    /// The target unit may follow the number, e.g. `8.05 km`.
+    fn check(&self, answer: &String) -> Result<bool> {
+        let answer = answer.trim();
+        let number = answer.strip_suffix(self.to.name.as_str()).unwrap_or(answer);
+        let answer = parse_real(&number.replace(' ', ""))?;
+        Ok(self.tolerance.matches(answer, self.solution()))
+    }
+
+    fn question_type(&self) -> &'static str {
+        "unit"
+    }
+
+    fn patterns(&self) -> Vec<String> {
+        vec![
+            String::from(self.question_type()),
+            format!("unit:{}", self.from.dimension),
+        ]
+    }
+
+    fn to_stored(&self) -> StoredQuestion {
+        StoredQuestion::Unit(self.clone())
+    }
+}
+
+/// `None` when no dimension has two units.
+pub fn random_conversion(units: &[Unit]) -> Option<Conversion> {
+    let mut rng = rand::thread_rng();
+    let convertible: Vec<&Unit> = units
+        .iter()
+        .filter(|unit| {
+            units
+                .iter()
+                .any(|other| other.dimension == unit.dimension && other.name != unit.name)
+        })
+        .collect();
+    let from = *convertible.choose(&mut rng)?;
+    let targets: Vec<&Unit> = convertible
+        .iter()
+        .copied()
+        .filter(|unit| unit.dimension == from.dimension && unit.name != from.name)
+        .collect();
+    let to = *targets.choose(&mut rng)?;
+    // a relative error means nothing around the zero of a shifted scale
+    let (value, tolerance) = if from.offset != 0.0 || to.offset != 0.0 {
+        (rng.gen_range(-40..=100) as f64, Tolerance::Round(0))
+    } else {
+        (rng.gen_range(1..=100) as f64, Tolerance::Relative(1.0))
+    };
+    Some(Conversion {
+        value,
+        from: from.clone(),
+        to: to.clone(),
+        tolerance,
+    })
+}
+
+#[test]
+fn unit_table() -> Result<()> {
+    let units = extend_units(
+        default_units(),
+        "[[unit]]\nname = \"nmi\"\ndimension = \"length\"\nfactor = 1852\n\n[[unit]]\nname = \"mi\"\ndimension = \"length\"\nfactor = 1600\n",
+    )?;
+    assert_eq!(units.len(), DEFAULT_UNITS.len() + 1);
+    let unit = |name: &str| units.iter().find(|unit| unit.name == name).unwrap().clone();
+    assert_eq!(unit("mi").factor, 1600.0);
+    let conversion = Conversion {
+        value: 100.0,
+        from: unit("°C"),
+        to: unit("°F"),
+        tolerance: Tolerance::Round(0),
+    };
+    assert!((conversion.solution() - 212.0).abs() < 1e-9);
+    assert!(extend_units(
+        vec![],
+        "[[unit]]\nname = \"x\"\ndimension = \"y\"\nfactor = 0"
+    )
+    .is_err());
+    assert!(random_conversion(&units[..1]).is_none());
+    Ok(())
+}
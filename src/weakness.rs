@@ -0,0 +1,128 @@
+use crate::{
+    generator::{random_question, GeneratorConfig, GENERATED_TYPES},
+    store::stats::{self as store_stats, PatternStats},
+    task::Question,
+};
+use anyhow::{Ok, Result};
+use rand::{seq::SliceRandom, Rng};
+use rusqlite::Connection;
+use std::cmp::Ordering;
+
+// patterns with fewer answers say nothing about a weakness yet
+const MIN_ATTEMPTS: i64 = 3;
+const WEAK_PATTERNS_COUNT: usize = 3;
+// the rest of the session is any question of a weak pattern's type
+const TARGETED_SHARE: f64 = 0.75;
+const GENERATION_ATTEMPTS: u32 = 1000;
+
+pub fn pattern_type(pattern: &str) -> &str {
+    pattern.split(':').next().unwrap_or(pattern)
+}
+
+/// Patterns with the worst accuracy first, the slowest first among equal ones.
+pub fn weakest_patterns(items: Vec<PatternStats>, count: usize) -> Vec<PatternStats> {
+    let mut items: Vec<PatternStats> = items
+        .into_iter()
+        .filter(|item| item.total >= MIN_ATTEMPTS)
+        .filter(|item| GENERATED_TYPES.contains(&pattern_type(&item.pattern)))
+        .collect();
+    items.sort_by(|left, right| {
+        accuracy(left)
+            .partial_cmp(&accuracy(right))
+            .unwrap_or(Ordering::Equal)
+            .then(
+                right
+                    .average_time_millis
+                    .partial_cmp(&left.average_time_millis)
+                    .unwrap_or(Ordering::Equal),
+            )
+    });
+    items.truncate(count);
+    items
+}
+
+pub fn targeted_question(pattern: &str, config: &GeneratorConfig) -> Option<Box<dyn Question>> {
+    let typ = pattern_type(pattern);
+    // inverse and signed questions are generated only when the session asks for them
+    let config = &GeneratorConfig {
+        missing_operands: config.missing_operands || pattern.ends_with(":inverse"),
+        signed: config.signed || pattern.ends_with(":signed"),
+        ..config.clone()
+    };
+    for _ in 0..GENERATION_ATTEMPTS {
+        let question = random_question(typ, config)?;
+        if question.patterns().iter().any(|item| item == pattern) {
+            return Some(question);
+        }
+    }
+    random_question(typ, config)
+}
+
+pub fn weak_questions(
+    connection: &Connection,
+    count: u32,
+    config: &GeneratorConfig,
+) -> Result<Vec<Box<dyn Question>>> {
+    store_stats::create_table_if_not_exist(connection)?;
+    let weakest = weakest_patterns(
+        store_stats::select_pattern_stats(connection)?,
+        WEAK_PATTERNS_COUNT,
+    );
+    let mut rng = rand::thread_rng();
+    let mut questions: Vec<Box<dyn Question>> = vec![];
+    if weakest.is_empty() {
+        return Ok(questions);
+    }
+    for _ in 0..count {
+        let pattern = &weakest.choose(&mut rng).unwrap().pattern;
+        let question = if rng.gen_bool(TARGETED_SHARE) {
+            targeted_question(pattern, config)
+        } else {
+            random_question(pattern_type(pattern), config)
+        };
+        if let Some(question) = question {
+            questions.push(question);
+        }
+    }
+    Ok(questions)
+}
+
+// partial credit makes a pattern less weak than a wrong answer
+fn accuracy(item: &PatternStats) -> f64 {
+    item.average_score
+}
+
+#[test]
+fn weakest_patterns_order() {
+    let item = |pattern: &str, total: i64, right: i64, average_time_millis: f64| PatternStats {
+        pattern: String::from(pattern),
+        total,
+        right,
+        average_time_millis,
+        average_score: right as f64 / total as f64,
+    };
+    let items = vec![
+        item("mul", 20, 18, 2000.0),
+        item("mul:7", 5, 2, 4000.0),
+        item("sub:borrow", 4, 2, 6000.0),
+        item("sum:carry", 1, 0, 9000.0),
+        item("mod:3", 10, 9, 1000.0),
+    ];
+    let patterns: Vec<String> = weakest_patterns(items, 3)
+        .into_iter()
+        .map(|item| item.pattern)
+        .collect();
+    assert_eq!(patterns, vec!["mul:7", "sub:borrow", "mul"]);
+}
+
+#[test]
+fn targeted_question_has_pattern() {
+    let question = targeted_question("mul:7", &GeneratorConfig::default()).unwrap();
+    assert!(question.patterns().contains(&String::from("mul:7")));
+    let question = targeted_question("missing:big", &GeneratorConfig::default()).unwrap();
+    assert!(question.patterns().contains(&String::from("missing:big")));
+    let question = targeted_question("div:inverse", &GeneratorConfig::default()).unwrap();
+    assert!(question.patterns().contains(&String::from("div:inverse")));
+    let question = targeted_question("sub:signed", &GeneratorConfig::default()).unwrap();
+    assert!(question.patterns().contains(&String::from("sub:signed")));
+}